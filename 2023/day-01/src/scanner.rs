@@ -0,0 +1,139 @@
+/// A digit found in a line, either written as a numeral or spelled out as a word.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DigitMatch {
+    pub pos: usize,
+    pub len: usize,
+    pub value: u32,
+}
+
+/// Forward scanner for numerals and a configurable table of spelled-out digits.
+///
+/// Matches may overlap, so `eightwo` yields both 8 and 2.
+#[derive(Debug, Clone)]
+pub struct DigitScanner {
+    words: Vec<(String, u32)>,
+}
+
+impl DigitScanner {
+    pub fn new(words: &[(&str, u32)]) -> Self {
+        DigitScanner {
+            words: words
+                .iter()
+                .map(|(word, value)| (word.to_string(), *value))
+                .collect(),
+        }
+    }
+
+    /// Only numerals count as digits.
+    pub fn numerals() -> Self {
+        DigitScanner::new(&[])
+    }
+
+    /// Numerals, as well as `one` through `nine`.
+    pub fn spelled() -> Self {
+        DigitScanner::new(&[
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ])
+    }
+
+    /// Every digit in `line`, in order of where it starts.
+    pub fn scan<'a>(&'a self, line: &'a str) -> impl Iterator<Item = DigitMatch> + 'a {
+        line.char_indices().flat_map(move |(pos, ch)| {
+            let numeral = ch.to_digit(10).map(|value| DigitMatch {
+                pos,
+                len: ch.len_utf8(),
+                value,
+            });
+
+            let rest = &line[pos..];
+            let words = self
+                .words
+                .iter()
+                .filter(move |(word, _)| rest.starts_with(word.as_str()))
+                .map(move |(word, value)| DigitMatch {
+                    pos,
+                    len: word.len(),
+                    value: *value,
+                });
+
+            numeral.into_iter().chain(words)
+        })
+    }
+
+    pub fn all(&self, line: &str) -> Vec<u32> {
+        self.scan(line).map(|m| m.value).collect()
+    }
+
+    pub fn first(&self, line: &str) -> Option<u32> {
+        self.scan(line).next().map(|m| m.value)
+    }
+
+    pub fn last(&self, line: &str) -> Option<u32> {
+        self.scan(line).last().map(|m| m.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::{DigitMatch, DigitScanner};
+
+    #[rstest]
+    #[case("eightwo", vec![8, 2])]
+    #[case("oneight3", vec![1, 8, 3])]
+    #[case("twone", vec![2, 1])]
+    #[case("abc", vec![])]
+    #[case("7pqrstsixteen", vec![7, 6])]
+    fn all_overlapping(#[case] input: &str, #[case] expected: Vec<u32>) {
+        assert_eq!(DigitScanner::spelled().all(input), expected);
+    }
+
+    #[test]
+    fn numerals_only() {
+        assert_eq!(DigitScanner::numerals().all("one2three4"), vec![2, 4]);
+    }
+
+    #[test]
+    fn custom_words() {
+        let scanner = DigitScanner::new(&[("uno", 1), ("dos", 2), ("cero", 0)]);
+
+        assert_eq!(scanner.all("cerodosuno5"), vec![0, 2, 1, 5]);
+        assert_eq!(scanner.first("xdos"), Some(2));
+        assert_eq!(scanner.last("unodosx"), Some(2));
+    }
+
+    #[test]
+    fn match_positions() {
+        let matches: Vec<DigitMatch> = DigitScanner::spelled().scan("x8eightwo").collect();
+
+        assert_eq!(
+            matches,
+            vec![
+                DigitMatch {
+                    pos: 1,
+                    len: 1,
+                    value: 8
+                },
+                DigitMatch {
+                    pos: 2,
+                    len: 5,
+                    value: 8
+                },
+                DigitMatch {
+                    pos: 6,
+                    len: 3,
+                    value: 2
+                },
+            ]
+        );
+    }
+}
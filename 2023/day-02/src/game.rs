@@ -0,0 +1,141 @@
+use std::str::FromStr;
+
+/// Cubes revealed from the bag in a single handful.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct Draw {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+impl Draw {
+    pub fn new(red: u32, green: u32, blue: u32) -> Self {
+        Draw { red, green, blue }
+    }
+}
+
+// 8 green, 6 blue, 20 red
+impl FromStr for Draw {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut draw = Draw::default();
+
+        for cube_color in value.split(',').map(|e| e.trim()) {
+            let (num, color) = cube_color
+                .split_once(' ')
+                .ok_or(format!("Expected '<num> <color>', found '{cube_color}'"))?;
+            let num = num
+                .parse::<u32>()
+                .map_err(|err| format!("Invalid cube count '{num}': {err}"))?;
+
+            match color {
+                "red" => draw.red = num,
+                "green" => draw.green = num,
+                "blue" => draw.blue = num,
+                _ => return Err(format!("Invalid color {color}")),
+            }
+        }
+
+        Ok(draw)
+    }
+}
+
+/// Number of cubes of each color loaded into the bag.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct Bag {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+impl Bag {
+    pub fn new(red: u32, green: u32, blue: u32) -> Self {
+        Bag { red, green, blue }
+    }
+
+    pub fn can_draw(&self, draw: &Draw) -> bool {
+        draw.red <= self.red && draw.green <= self.green && draw.blue <= self.blue
+    }
+
+    pub fn power(&self) -> u32 {
+        self.red * self.green * self.blue
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<Draw>,
+}
+
+impl Game {
+    pub fn is_possible_with(&self, bag: &Bag) -> bool {
+        self.draws.iter().all(|draw| bag.can_draw(draw))
+    }
+
+    /// Fewest cubes of each color that make every draw in this game possible.
+    pub fn minimal_bag(&self) -> Bag {
+        self.draws.iter().fold(Bag::default(), |bag, draw| Bag {
+            red: bag.red.max(draw.red),
+            green: bag.green.max(draw.green),
+            blue: bag.blue.max(draw.blue),
+        })
+    }
+
+    pub fn power(&self) -> u32 {
+        self.minimal_bag().power()
+    }
+}
+
+// Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (game, draws) = line
+            .split_once(':')
+            .ok_or(format!("Expected 'Game <num>: <draws>', found '{line}'"))?;
+
+        let id = game
+            .strip_prefix("Game ")
+            .ok_or(format!("Expected 'Game <num>', found '{game}'"))?
+            .parse::<u32>()
+            .map_err(|err| format!("Invalid game number '{game}': {err}"))?;
+
+        let draws = draws
+            .trim()
+            .split(';')
+            .map(Draw::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Game { id, draws })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bag, Draw, Game};
+
+    #[test]
+    fn parse_game() {
+        let game: Game = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+            .parse()
+            .unwrap();
+
+        assert_eq!(game.id, 3);
+        assert_eq!(
+            game.draws,
+            vec![Draw::new(20, 8, 6), Draw::new(4, 13, 5), Draw::new(1, 5, 0)]
+        );
+        assert_eq!(game.minimal_bag(), Bag::new(20, 13, 6));
+        assert!(!game.is_possible_with(&Bag::new(12, 13, 14)));
+        assert!(game.is_possible_with(&Bag::new(20, 13, 6)));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("Game 1: 3 purple".parse::<Game>().is_err());
+        assert!("Round 1: 3 red".parse::<Game>().is_err());
+    }
+}
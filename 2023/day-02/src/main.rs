@@ -1,129 +1,16 @@
-use std::{env, fs::read_to_string};
-
-#[derive(Debug)]
-pub struct Row {
-    game_num: Option<u32>,
-    max_red: u32,
-    max_blue: u32,
-    max_green: u32,
-}
-
-impl Row {
-    pub fn new() -> Row {
-        Row {
-            game_num: None,
-            max_red: 0,
-            max_blue: 0,
-            max_green: 0,
-        }
-    }
-
-    pub fn is_possible(&self) -> bool {
-        self.max_red <= 12 && self.max_green <= 13 && self.max_blue <= 14
-    }
-
-    pub fn power(&self) -> u32 {
-        self.max_blue * self.max_green * self.max_red
-    }
-}
-
-#[derive(Debug)]
-struct Game {
-    blue: Option<u32>,
-    green: Option<u32>,
-    red: Option<u32>,
-}
-
-// Game 5
-fn parse_game_number(game: &str) -> u32 {
-    let mut split: Vec<_> = game.split(" ").collect();
+pub mod game;
 
-    return split
-        .pop()
-        .expect("format Game <num>")
-        .parse::<u32>()
-        .expect("expected game number");
-}
-
-// 8 green, 6 blue, 20 red
-fn parse_game(game: &str) -> Game {
-    let mut result = Game {
-        blue: None,
-        green: None,
-        red: None,
-    };
-
-    let cube_colors = game.split(",").map(|e| e.trim());
-    for cube_color in cube_colors {
-        let cubes: Vec<_> = cube_color.split(" ").collect();
-        let num = cubes
-            .get(0)
-            .expect("format <num> color")
-            .parse::<u32>()
-            .expect("expected cube number");
-        let color = *cubes.get(1).expect("format num <color>");
-        match color {
-            "blue" => result.blue = Some(num),
-            "green" => result.green = Some(num),
-            "red" => result.red = Some(num),
-            &_ => panic!("Invalid color {color}"),
-        }
-    }
-
-    result
-}
-
-fn parse(line: &String) -> Row {
-    let mut row = Row::new();
-
-    let mut game: Vec<_> = line.split(":").collect();
-
-    let games: Vec<_> = game
-        .pop()
-        .expect("list of games")
-        .trim()
-        .split(";")
-        .collect();
-
-    for game in games {
-        let parsed_game = parse_game(game);
-        // println!("\t{game:?}");
-
-        match parsed_game.blue {
-            Some(num) => {
-                if num > row.max_blue {
-                    row.max_blue = num
-                }
-            }
-            None => {}
-        }
-
-        match parsed_game.green {
-            Some(num) => {
-                if num > row.max_green {
-                    row.max_green = num
-                }
-            }
-            None => {}
-        }
-
-        match parsed_game.red {
-            Some(num) => {
-                if num > row.max_red {
-                    row.max_red = num
-                }
-            }
-            None => {}
-        }
-    }
-
-    row.game_num = Some(parse_game_number(
-        game.pop().expect("expected game num split"),
-    ));
-    // println!("game num: {:?}", row.game_num);
+use game::{Bag, Game};
+use std::{env, fs::read_to_string};
 
-    // println!("\trow: {row:?}");
-    row
+fn parse(lines: &[String]) -> Vec<Game> {
+    lines
+        .iter()
+        .map(|line| match line.parse::<Game>() {
+            Ok(game) => game,
+            Err(err) => panic!("Invalid game '{line}': {err}"),
+        })
+        .collect()
 }
 
 fn read_lines(filename: &str) -> Vec<String> {
@@ -134,38 +21,66 @@ fn read_lines(filename: &str) -> Vec<String> {
         .collect()
 }
 
-fn solve_part1(lines: Vec<String>) -> u32 {
-    let mut total: u32 = 0;
-    for line in lines.iter() {
-        let row = parse(line);
-        if row.is_possible() {
-            total += row.game_num.unwrap();
-        }
-    }
-    total
+fn solve_part1(lines: Vec<String>, bag: &Bag) -> u32 {
+    parse(&lines)
+        .iter()
+        .filter(|game| game.is_possible_with(bag))
+        .map(|game| game.id)
+        .sum()
 }
 
 fn solve_part2(lines: Vec<String>) -> u32 {
-    lines
+    parse(&lines).iter().map(Game::power).sum()
+}
+
+// --bag <red> <green> <blue>
+fn parse_bag(args: &[String]) -> Result<Bag, String> {
+    let counts = args
         .iter()
-        .map(|line| -> u32 { parse(line).power() })
-        .sum()
+        .map(|arg| {
+            arg.parse::<u32>()
+                .map_err(|err| format!("Invalid bag count '{arg}': {err}"))
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    match counts[..] {
+        [red, green, blue] => Ok(Bag::new(red, green, blue)),
+        _ => Err(format!(
+            "Expected 3 bag counts (red green blue), found {}",
+            counts.len()
+        )),
+    }
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    match args.len() {
-        0..=1 => println!("Pass in filename to solve and part"),
-        3 => println!(
-            "Solution for part 2 for {} is {}",
-            args[1].clone(),
-            solve_part2(read_lines(&args[1].clone()))
-        ),
-        _ => println!(
+    if args.len() <= 1 {
+        println!("Pass in filename to solve and part, or filename and --bag <red> <green> <blue>");
+        return;
+    }
+
+    match args.get(2).map(String::as_str) {
+        None => println!(
             "Solution for part 1 for {} is {}",
-            args[1].clone(),
-            solve_part1(read_lines(&args[1].clone()))
+            args[1],
+            solve_part1(read_lines(&args[1]), &Bag::new(12, 13, 14))
+        ),
+        Some("--bag") => match parse_bag(&args[3..]) {
+            Ok(bag) => println!(
+                "Solution for part 1 for {} is {}",
+                args[1],
+                solve_part1(read_lines(&args[1]), &bag)
+            ),
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        },
+        Some(_) => println!(
+            "Solution for part 2 for {} is {}",
+            args[1],
+            solve_part2(read_lines(&args[1]))
         ),
     }
 }
@@ -175,6 +90,7 @@ mod test {
 
     use super::solve_part1;
     use super::solve_part2;
+    use super::{parse_bag, Bag};
 
     #[test]
     fn solve_example() {
@@ -188,7 +104,9 @@ mod test {
         .map(String::from)
         .to_vec();
 
-        assert_eq!(solve_part1(rows), 8);
+        assert_eq!(solve_part1(rows.clone(), &Bag::new(12, 13, 14)), 8);
+        assert_eq!(solve_part1(rows.clone(), &Bag::new(20, 13, 15)), 15);
+        assert_eq!(solve_part1(rows, &Bag::new(1, 1, 1)), 0);
     }
 
     #[test]
@@ -205,4 +123,14 @@ mod test {
 
         assert_eq!(solve_part2(rows), 2286);
     }
+
+    #[test]
+    fn parse_bag_args() {
+        let args = ["20", "13", "15"].map(String::from);
+        assert_eq!(parse_bag(&args), Ok(Bag::new(20, 13, 15)));
+
+        assert!(parse_bag(&args[..2]).is_err());
+        assert!(parse_bag(&["1", "2", "3", "4"].map(String::from)).is_err());
+        assert!(parse_bag(&["1", "two", "3"].map(String::from)).is_err());
+    }
 }
@@ -1,3 +1,6 @@
+pub mod schematic;
+
+use schematic::Schematic;
 use std::{env, fs::read_to_string};
 
 fn read_lines(filename: &str) -> Vec<String> {
@@ -8,78 +11,10 @@ fn read_lines(filename: &str) -> Vec<String> {
         .collect()
 }
 
-fn is_symbol(ch: Option<&char>) -> bool {
-    match ch {
-        Some(c) => !c.is_alphanumeric() && *c != '.',
-        None => false,
-    }
-}
-
-fn has_adjacent_symbol(col: usize, line: Option<&Vec<char>>) -> bool {
-    match line {
-        Some(chars) => {
-            let prev: bool = if col > 0 {
-                is_symbol(chars.get(col - 1))
-            } else {
-                false
-            };
-
-            prev || is_symbol(chars.get(col)) || is_symbol(chars.get(col + 1))
-        }
-        None => false,
-    }
-}
-
-fn get_num_if_adjacent(num_str: Option<String>, is_adjacent: bool) -> u32 {
-    match num_str {
-        Some(num) => match is_adjacent {
-            true => num.parse::<u32>().unwrap(),
-            false => 0,
-        },
-        None => 0,
-    }
-}
-
 fn solve(lines: Vec<String>) -> u32 {
-    let mut total: u32 = 0;
-
-    let rows: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
-    for (row, line) in rows.iter().enumerate() {
-        // println!("{row}");
-
-        let mut current_num: Option<String> = None;
-        let mut is_adjacent = false;
-        for (col, ch) in line.iter().enumerate() {
-            // println!("\t{col} {ch}");
-            if ch.is_numeric() {
-                match current_num.as_mut() {
-                    Some(num) => num.push(ch.clone()),
-                    None => current_num = Some(ch.to_string()),
-                }
-
-                let previous_row = if row > 0 { rows.get(row - 1) } else { None };
-                let next_row = rows.get(row + 1);
-
-                is_adjacent = is_adjacent
-                    || has_adjacent_symbol(col, previous_row)
-                    || has_adjacent_symbol(col, next_row);
-            } else {
-                is_adjacent = is_adjacent || is_symbol(Some(ch));
-                total += get_num_if_adjacent(current_num, is_adjacent);
-
-                // Reset
-                current_num = None;
-                is_adjacent = is_symbol(Some(ch));
-            }
-
-            // println!("{row} {col} {ch}: {is_adjacent}");
-        }
-
-        // Handle case at the end of the row
-        total += get_num_if_adjacent(current_num, is_adjacent);
-    }
+    let schematic = Schematic::from(&lines[..]);
 
-    total
+    schematic.part_numbers().map(|number| number.value).sum()
 }
 
 fn main() {
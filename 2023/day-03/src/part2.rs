@@ -1,7 +1,7 @@
-use std::{env, fs::read_to_string};
+pub mod schematic;
 
-use once_cell::sync::Lazy;
-use regex::Regex;
+use schematic::Schematic;
+use std::{env, fs::read_to_string};
 
 fn read_lines(filename: &str) -> Vec<String> {
     read_to_string(filename)
@@ -11,93 +11,14 @@ fn read_lines(filename: &str) -> Vec<String> {
         .collect()
 }
 
-fn starts_with_num(s: &str) -> Option<&str> {
-    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([0-9]+)").unwrap());
-    match RE.captures(s) {
-        Some(capture) => Some(capture.get(1).unwrap().as_str()),
-        None => None,
-    }
-}
-
-fn ends_with_num(s: &str) -> Option<&str> {
-    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"([0-9]+)$").unwrap());
-    match RE.captures(s) {
-        Some(capture) => Some(capture.get(1).unwrap().as_str()),
-        None => None,
-    }
-}
-
-fn get_adjacent_nums(col: usize, row: Option<&String>) -> Vec<u32> {
-    match row {
-        None => {}
-        Some(row) => {
-            let ch = row.chars().nth(col).unwrap();
-            match ch.is_ascii_digit() {
-                true => {
-                    let (lhs, rhs) = row.split_at(col);
-                    let mut result: String = "".to_string();
-                    match ends_with_num(lhs) {
-                        Some(num_str) => {
-                            result.push_str(num_str);
-                        }
-                        None => {}
-                    }
-
-                    match starts_with_num(rhs) {
-                        Some(num_str) => {
-                            result.push_str(num_str);
-                        }
-                        None => {
-                            println!("rhs doesn't start with num: {rhs} col:{col}");
-                        }
-                    }
-
-                    return vec![result.parse::<u32>().unwrap()];
-                }
-                false => {
-                    let (lhs, rhs) = row.split_at(col);
-
-                    return vec![ends_with_num(lhs), starts_with_num(rhs.get(1..).unwrap())]
-                        .into_iter()
-                        .filter_map(|v| v)
-                        .collect::<Vec<&str>>()
-                        .into_iter()
-                        .map(|s| s.parse::<u32>().unwrap())
-                        .collect();
-                }
-            }
-        }
-    }
-
-    vec![]
-}
-
-fn solve(rows: Vec<String>) -> u32 {
-    let mut total: u32 = 0;
-
-    for (row_num, row) in rows.iter().enumerate() {
-        let previous_row = match row_num > 0 {
-            true => rows.get(row_num - 1),
-            false => None,
-        };
-        let next_row = rows.get(row_num + 1);
-
-        for (col_num, ch) in row.chars().enumerate() {
-            if ch != '*' {
-                continue;
-            }
-
-            let mut adjacent_nums: Vec<u32> = get_adjacent_nums(col_num, Some(row));
-            adjacent_nums.append(&mut get_adjacent_nums(col_num, previous_row));
-            adjacent_nums.append(&mut get_adjacent_nums(col_num, next_row));
-
-            if adjacent_nums.len() == 2 {
-                total += adjacent_nums.into_iter().product::<u32>();
-            }
-        }
-    }
+fn solve(lines: Vec<String>) -> u32 {
+    let schematic = Schematic::from(&lines[..]);
 
-    total
+    schematic
+        .gears()
+        .into_iter()
+        .map(|(_, [lhs, rhs])| lhs.value * rhs.value)
+        .sum()
 }
 
 fn main() {
@@ -0,0 +1,227 @@
+use std::{collections::HashMap, ops::Range};
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct Coordinate {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coordinate {
+    pub fn new(row: usize, col: usize) -> Self {
+        Coordinate { row, col }
+    }
+}
+
+/// A number in the schematic, covering `cols` on a single `row`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NumberSpan {
+    pub row: usize,
+    pub cols: Range<usize>,
+    pub value: u32,
+}
+
+impl NumberSpan {
+    /// Every coordinate touching the number, including diagonally.
+    fn neighbors(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        let rows = self.row.saturating_sub(1)..=self.row + 1;
+        rows.flat_map(move |row| {
+            (self.cols.start.saturating_sub(1)..=self.cols.end)
+                .map(move |col| Coordinate::new(row, col))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Symbol {
+    pub coord: Coordinate,
+    pub ch: char,
+}
+
+impl Symbol {
+    pub fn is_symbol(ch: char) -> bool {
+        !ch.is_alphanumeric() && ch != '.'
+    }
+}
+
+/// Parsed engine schematic, with an index of which numbers and symbols are adjacent.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    numbers: Vec<NumberSpan>,
+    symbols: Vec<Symbol>,
+    // Indices into `symbols` for each number, and into `numbers` for each symbol
+    number_symbols: Vec<Vec<usize>>,
+    symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    pub fn numbers(&self) -> &[NumberSpan] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn symbols_adjacent_to(&self, number: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_symbols[number]
+            .iter()
+            .map(|symbol| &self.symbols[*symbol])
+    }
+
+    pub fn numbers_adjacent_to(&self, symbol: usize) -> impl Iterator<Item = &NumberSpan> {
+        self.symbol_numbers[symbol]
+            .iter()
+            .map(|number| &self.numbers[*number])
+    }
+
+    /// Numbers adjacent to exactly `count` symbols.
+    pub fn numbers_with_symbols(&self, count: usize) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers
+            .iter()
+            .zip(self.number_symbols.iter())
+            .filter(move |(_, symbols)| symbols.len() == count)
+            .map(|(number, _)| number)
+    }
+
+    /// Numbers adjacent to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers
+            .iter()
+            .zip(self.number_symbols.iter())
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /// Every `*` adjacent to exactly two numbers, along with those numbers.
+    pub fn gears(&self) -> Vec<(&Symbol, [&NumberSpan; 2])> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| symbol.ch == '*')
+            .filter_map(|(pos, symbol)| match self.symbol_numbers[pos][..] {
+                [lhs, rhs] => Some((symbol, [&self.numbers[lhs], &self.numbers[rhs]])),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl From<&[String]> for Schematic {
+    fn from(lines: &[String]) -> Self {
+        let mut numbers: Vec<NumberSpan> = Vec::new();
+        let mut symbols: Vec<Symbol> = Vec::new();
+
+        for (row, line) in lines.iter().enumerate() {
+            let mut current: Option<NumberSpan> = None;
+
+            for (col, ch) in line.chars().enumerate() {
+                match ch.to_digit(10) {
+                    Some(digit) => match current.as_mut() {
+                        Some(number) => {
+                            number.cols.end = col + 1;
+                            number.value = number.value * 10 + digit;
+                        }
+                        None => {
+                            current = Some(NumberSpan {
+                                row,
+                                cols: col..col + 1,
+                                value: digit,
+                            })
+                        }
+                    },
+                    None => {
+                        numbers.extend(current.take());
+                        if Symbol::is_symbol(ch) {
+                            symbols.push(Symbol {
+                                coord: Coordinate::new(row, col),
+                                ch,
+                            });
+                        }
+                    }
+                }
+            }
+
+            // Handle case at the end of the row
+            numbers.extend(current.take());
+        }
+
+        let symbol_at: HashMap<Coordinate, usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(pos, symbol)| (symbol.coord, pos))
+            .collect();
+
+        let mut number_symbols: Vec<Vec<usize>> = vec![Vec::new(); numbers.len()];
+        let mut symbol_numbers: Vec<Vec<usize>> = vec![Vec::new(); symbols.len()];
+        for (number_pos, number) in numbers.iter().enumerate() {
+            for coord in number.neighbors() {
+                if let Some(symbol_pos) = symbol_at.get(&coord) {
+                    number_symbols[number_pos].push(*symbol_pos);
+                    symbol_numbers[*symbol_pos].push(number_pos);
+                }
+            }
+        }
+
+        Schematic {
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Coordinate, NumberSpan, Schematic};
+
+    const EXAMPLE: [&str; 10] = [
+        "467..114..",
+        "...*......",
+        "..35..633.",
+        "......#...",
+        "617*......",
+        ".....+.58.",
+        "..592.....",
+        "......755.",
+        "...$.*....",
+        ".664.598..",
+    ];
+
+    #[test]
+    fn parse_numbers_and_symbols() {
+        let schematic = Schematic::from(&EXAMPLE.map(String::from)[..]);
+
+        assert_eq!(schematic.numbers().len(), 10);
+        assert_eq!(
+            schematic.numbers()[0],
+            NumberSpan {
+                row: 0,
+                cols: 0..3,
+                value: 467
+            }
+        );
+        assert_eq!(schematic.symbols().len(), 6);
+        assert_eq!(schematic.symbols()[0].coord, Coordinate::new(1, 3));
+        assert_eq!(schematic.symbols()[0].ch, '*');
+    }
+
+    #[test]
+    fn numbers_at_row_edges() {
+        let rows = ["12*34", "5...6", "*..78"].map(String::from);
+        let schematic = Schematic::from(&rows[..]);
+
+        let values: Vec<u32> = schematic.part_numbers().map(|n| n.value).collect();
+        assert_eq!(values, vec![12, 34, 5]);
+    }
+
+    #[test]
+    fn numbers_adjacent_to_multiple_symbols() {
+        let rows = ["*.#..", ".12..", "..$.."].map(String::from);
+        let schematic = Schematic::from(&rows[..]);
+
+        let values: Vec<u32> = schematic.numbers_with_symbols(3).map(|n| n.value).collect();
+        assert_eq!(values, vec![12]);
+        assert_eq!(schematic.numbers_with_symbols(1).count(), 0);
+    }
+}
@@ -0,0 +1,62 @@
+pub mod scratchcards;
+
+use scratchcards::{parse, total_points, Card, Cascade};
+use std::{env, fs::read_to_string};
+
+fn solve(cards: &[Card]) -> Result<(u64, u32), String> {
+    Ok((total_points(cards)?, Cascade::from(cards).total()))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    match args.len() {
+        0..=1 => println!("Pass in filename to solve, and --report to show the cascade"),
+        _ => {
+            let cards = parse(&read_lines(&args[1]));
+            let (part1, part2) = match solve(&cards) {
+                Ok(solution) => solution,
+                Err(err) => {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                }
+            };
+
+            if args[2..].iter().any(|arg| arg == "--report") {
+                print!("{}", Cascade::from(&cards[..]));
+            }
+
+            println!("Solution for part 1 for {} is {}", args[1], part1);
+            println!("Solution for part 2 for {} is {}", args[1], part2);
+        }
+    }
+}
+
+fn read_lines(filename: &str) -> Vec<String> {
+    read_to_string(filename)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{parse, solve};
+
+    #[test]
+    fn solve_example() {
+        let rows = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]
+        .map(String::from);
+
+        assert_eq!(solve(&parse(&rows)), Ok((13, 30)));
+    }
+}
@@ -0,0 +1,226 @@
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: HashSet<u32>,
+    pub have: Vec<u32>,
+}
+
+impl Card {
+    pub fn num_winners(&self) -> usize {
+        self.have
+            .iter()
+            .filter(|number| self.winning.contains(number))
+            .count()
+    }
+
+    /// One point for the first match, doubled for every match after that.
+    ///
+    /// Fails if the card has too many matches for its points to fit in a `u64`.
+    pub fn points(&self) -> Result<u64, String> {
+        match self.num_winners() {
+            0 => Ok(0),
+            num_winners => u32::try_from(num_winners - 1)
+                .ok()
+                .and_then(|shift| 1_u64.checked_shl(shift))
+                .ok_or(format!(
+                    "Card {} has {num_winners} matches, too many points to count",
+                    self.id
+                )),
+        }
+    }
+}
+
+fn parse_numbers(value: &str) -> Result<Vec<u32>, String> {
+    value
+        .split_whitespace()
+        .map(|num_str| {
+            num_str
+                .parse::<u32>()
+                .map_err(|err| format!("Invalid number '{num_str}': {err}"))
+        })
+        .collect()
+}
+
+// Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (card, numbers) = line
+            .split_once(':')
+            .ok_or(format!("Expected 'Card <id>: <numbers>', found '{line}'"))?;
+        let (winning, have) = numbers
+            .split_once('|')
+            .ok_or(format!("Expected '<winning> | <have>', found '{numbers}'"))?;
+
+        let id = card
+            .strip_prefix("Card")
+            .map(str::trim)
+            .ok_or(format!("Expected 'Card <id>', found '{card}'"))?
+            .parse::<u32>()
+            .map_err(|err| format!("Invalid card id '{card}': {err}"))?;
+
+        Ok(Card {
+            id,
+            winning: parse_numbers(winning)?.into_iter().collect(),
+            have: parse_numbers(have)?,
+        })
+    }
+}
+
+pub fn parse(lines: &[String]) -> Vec<Card> {
+    lines
+        .iter()
+        .map(|line| match line.parse::<Card>() {
+            Ok(card) => card,
+            Err(err) => panic!("Invalid card '{line}': {err}"),
+        })
+        .collect()
+}
+
+pub fn total_points(cards: &[Card]) -> Result<u64, String> {
+    cards.iter().try_fold(0_u64, |total, card| {
+        total
+            .checked_add(card.points()?)
+            .ok_or(format!("Total points overflowed at card {}", card.id))
+    })
+}
+
+/// How copies of each card propagate, where each match on a card wins one copy
+/// of each following card for every copy held.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cascade {
+    ids: Vec<u32>,
+    winners: Vec<usize>,
+    copies: Vec<u32>,
+}
+
+impl Cascade {
+    pub fn copies(&self) -> &[u32] {
+        &self.copies
+    }
+
+    pub fn total(&self) -> u32 {
+        self.copies.iter().sum()
+    }
+}
+
+impl From<&[Card]> for Cascade {
+    fn from(cards: &[Card]) -> Self {
+        let winners: Vec<usize> = cards.iter().map(Card::num_winners).collect();
+        let mut copies = vec![1_u32; cards.len()];
+
+        for (pos, num_winners) in winners.iter().enumerate() {
+            let num_copies = copies[pos];
+            let end = (pos + 1 + num_winners).min(cards.len());
+            for copy in copies[pos + 1..end].iter_mut() {
+                *copy += num_copies;
+            }
+        }
+
+        Cascade {
+            ids: cards.iter().map(|card| card.id).collect(),
+            winners,
+            copies,
+        }
+    }
+}
+
+impl Display for Cascade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (pos, id) in self.ids.iter().enumerate() {
+            write!(
+                f,
+                "Card {id}: {} copies, {} matches",
+                self.copies[pos], self.winners[pos]
+            )?;
+
+            let won: Vec<String> = self.ids[pos + 1..]
+                .iter()
+                .take(self.winners[pos])
+                .map(|id| id.to_string())
+                .collect();
+            match won.is_empty() {
+                true => writeln!(f)?,
+                false => writeln!(
+                    f,
+                    ", wins {} copies of cards {}",
+                    self.copies[pos],
+                    won.join(", ")
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, total_points, Card, Cascade};
+
+    const EXAMPLE: [&str; 6] = [
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+        "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+        "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+        "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+        "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+        "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+    ];
+
+    #[test]
+    fn parse_card() {
+        let card: Card = EXAMPLE[2].parse().unwrap();
+
+        assert_eq!(card.id, 3);
+        assert_eq!(card.winning.len(), 5);
+        assert!(card.winning.contains(&59));
+        assert_eq!(card.have, vec![69, 82, 63, 72, 16, 21, 14, 1]);
+        assert_eq!(card.points(), Ok(2));
+    }
+
+    #[test]
+    fn both_parts_from_one_parse() {
+        let cards = parse(&EXAMPLE.map(String::from));
+        let cascade = Cascade::from(&cards[..]);
+
+        assert_eq!(total_points(&cards), Ok(13));
+        assert_eq!(cascade.copies(), &[1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.total(), 30);
+    }
+
+    #[test]
+    fn cascade_report() {
+        let cards = parse(&EXAMPLE.map(String::from));
+        let report = Cascade::from(&cards[..]).to_string();
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "Card 1: 1 copies, 4 matches, wins 1 copies of cards 2, 3, 4, 5"
+        );
+        assert_eq!(lines[5], "Card 6: 1 copies, 0 matches");
+    }
+
+    #[test]
+    fn points_for_many_matches() {
+        let numbers = |count: u32| {
+            (1..=count)
+                .map(|num| num.to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        let card = |count: u32| -> Card {
+            format!("Card 1: {} | {}", numbers(count), numbers(count))
+                .parse()
+                .unwrap()
+        };
+
+        assert_eq!(card(33).points(), Ok(1 << 32));
+        assert_eq!(card(64).points(), Ok(1 << 63));
+        assert!(card(65).points().is_err());
+        assert!(total_points(&[card(64), card(64)]).is_err());
+    }
+}
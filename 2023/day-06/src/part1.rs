@@ -1,50 +1,26 @@
-use std::{env, fs::read_to_string};
-
-#[derive(Debug)]
-struct Race {
-    elapsed: u32,
-    distance: u32,
-}
-
-impl Race {
-    pub fn num_ways(&self) -> u32 {
-        let mut result: u32 = 0;
-        let mut push_elapsed: u32 = 1;
-        while push_elapsed < self.elapsed {
-            let travel_elapsed = self.elapsed - push_elapsed;
-            let travel_distance = travel_elapsed * push_elapsed; // push elapsed is equal to the rate, since it's 1mm/ms per ms pushed
-            if travel_distance > self.distance {
-                result += 1;
-            }
-
-            push_elapsed += 1;
-        }
+pub mod race;
 
-        result
-    }
-}
+use race::Race;
+use std::{env, fs::read_to_string};
 
-fn parse_line(line: String) -> Vec<u32> {
+fn parse_line(line: String) -> Vec<u64> {
     let splits: Vec<&str> = line.split(": ").collect();
     let values: Vec<_> = splits.get(1).unwrap().split_whitespace().collect();
-    values.iter().map(|v| v.parse::<u32>().unwrap()).collect()
+    values.iter().map(|v| v.parse::<u64>().unwrap()).collect()
 }
 
 /// Returns a sum of all the numbers passed in
-fn solve(lines: Vec<String>) -> u32 {
+fn solve(lines: Vec<String>) -> u64 {
     let mut input = lines.into_iter();
     let times = parse_line(input.next().unwrap());
     let distances = parse_line(input.next().unwrap());
 
     let mut races: Vec<Race> = Vec::new();
     for (pos, time) in times.into_iter().enumerate() {
-        races.push(Race {
-            elapsed: time,
-            distance: distances[pos],
-        })
+        races.push(Race::new(time, distances[pos]))
     }
 
-    let num_ways: Vec<u32> = races
+    let num_ways: Vec<u64> = races
         .into_iter()
         .map(|race| {
             let num_ways = race.num_ways();
@@ -1,28 +1,7 @@
-use std::{env, fs::read_to_string};
-
-#[derive(Debug)]
-struct Race {
-    elapsed: u64,
-    distance: u64,
-}
-
-impl Race {
-    pub fn num_ways(&self) -> u64 {
-        let mut result: u64 = 0;
-        let mut push_elapsed: u64 = 1;
-        while push_elapsed < self.elapsed {
-            let travel_elapsed = self.elapsed - push_elapsed;
-            let travel_distance = travel_elapsed * push_elapsed; // push elapsed is equal to the rate, since it's 1mm/ms per ms pushed
-            if travel_distance > self.distance {
-                result += 1;
-            }
+pub mod race;
 
-            push_elapsed += 1;
-        }
-
-        result
-    }
-}
+use race::Race;
+use std::{env, fs::read_to_string};
 
 fn parse_line(line: String) -> u64 {
     let splits: Vec<&str> = line.split(": ").collect();
@@ -35,10 +14,10 @@ fn parse_line(line: String) -> u64 {
 fn solve(lines: Vec<String>) -> u64 {
     let mut input = lines.into_iter();
 
-    let race = Race {
-        elapsed: parse_line(input.next().unwrap()),
-        distance: parse_line(input.next().unwrap()),
-    };
+    let race = Race::new(
+        parse_line(input.next().unwrap()),
+        parse_line(input.next().unwrap()),
+    );
 
     race.num_ways()
 }
@@ -0,0 +1,99 @@
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Race {
+    pub elapsed: u64,
+    pub distance: u64,
+}
+
+impl Race {
+    pub fn new(elapsed: u64, distance: u64) -> Self {
+        Race { elapsed, distance }
+    }
+
+    // Holding for `hold` ms sets the rate to `hold` mm/ms for the remaining time
+    fn travels(&self, hold: u128) -> u128 {
+        hold * (self.elapsed as u128 - hold)
+    }
+
+    fn wins(&self, hold: u128) -> bool {
+        self.travels(hold) > self.distance as u128
+    }
+
+    /// Every hold time that beats the record, solving `hold * (elapsed - hold) > distance`
+    /// exactly rather than trying each hold time.
+    pub fn winning_holds(&self) -> Option<RangeInclusive<u64>> {
+        let elapsed = self.elapsed as u128;
+        let distance = self.distance as u128;
+
+        // Roots of hold^2 - elapsed * hold + distance = 0
+        let discriminant = (elapsed * elapsed).checked_sub(4 * distance)?;
+        let mut lowest = (elapsed - discriminant.isqrt()) / 2;
+
+        // The integer square root can be off by one either way, so nudge onto the boundary
+        while lowest <= elapsed / 2 && !self.wins(lowest) {
+            lowest += 1;
+        }
+        while lowest > 0 && self.wins(lowest - 1) {
+            lowest -= 1;
+        }
+
+        if lowest > elapsed / 2 || !self.wins(lowest) {
+            return None;
+        }
+
+        // The curve is symmetric around elapsed / 2
+        let highest = elapsed - lowest;
+        Some(lowest as u64..=highest as u64)
+    }
+
+    pub fn num_ways(&self) -> u64 {
+        match self.winning_holds() {
+            Some(holds) => holds.end() - holds.start() + 1,
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Race;
+
+    fn brute_force(race: &Race) -> u64 {
+        (0..=race.elapsed)
+            .filter(|hold| hold * (race.elapsed - hold) > race.distance)
+            .count() as u64
+    }
+
+    #[test]
+    fn winning_holds() {
+        assert_eq!(Race::new(7, 9).winning_holds(), Some(2..=5));
+        assert_eq!(Race::new(15, 40).winning_holds(), Some(4..=11));
+        assert_eq!(Race::new(30, 200).winning_holds(), Some(11..=19));
+        assert_eq!(Race::new(71530, 940200).winning_holds(), Some(14..=71516));
+    }
+
+    #[test]
+    fn no_winning_holds() {
+        assert_eq!(Race::new(4, 4).winning_holds(), None);
+        assert_eq!(Race::new(3, 100).winning_holds(), None);
+        assert_eq!(Race::new(0, 0).winning_holds(), None);
+        assert_eq!(Race::new(4, 4).num_ways(), 0);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for elapsed in 0..60 {
+            for distance in 0..(elapsed * elapsed / 4 + 2) {
+                let race = Race::new(elapsed, distance);
+                assert_eq!(race.num_ways(), brute_force(&race), "{race:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn large_values() {
+        let race = Race::new(u64::MAX, u64::MAX);
+        assert_eq!(race.winning_holds(), Some(2..=u64::MAX - 2));
+    }
+}
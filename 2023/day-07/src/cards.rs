@@ -0,0 +1,334 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use strum_macros::{Display, EnumString};
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, EnumString, Display, Clone, Copy, Hash)]
+pub enum Card {
+    #[strum(serialize = "2")]
+    Two,
+
+    #[strum(serialize = "3")]
+    Three,
+
+    #[strum(serialize = "4")]
+    Four,
+
+    #[strum(serialize = "5")]
+    Five,
+
+    #[strum(serialize = "6")]
+    Six,
+
+    #[strum(serialize = "7")]
+    Seven,
+
+    #[strum(serialize = "8")]
+    Eight,
+
+    #[strum(serialize = "9")]
+    Nine,
+
+    #[strum(serialize = "T")]
+    Ten,
+
+    #[strum(serialize = "J")]
+    Jack,
+
+    #[strum(serialize = "Q")]
+    Queen,
+
+    #[strum(serialize = "K")]
+    King,
+
+    #[strum(serialize = "A")]
+    Ace,
+}
+
+/// Rules for a game of Camel Cards: the order of the cards from weakest to
+/// strongest, which card (if any) is wild, and how many cards make a hand.
+#[derive(Debug, Clone)]
+pub struct Ruleset {
+    order: Vec<Card>,
+    wild: Option<Card>,
+    hand_size: usize,
+}
+
+impl Ruleset {
+    pub fn new(order: Vec<Card>, wild: Option<Card>, hand_size: usize) -> Self {
+        Ruleset {
+            order,
+            wild,
+            hand_size,
+        }
+    }
+
+    /// Part 1 rules, where `J` is a Jack.
+    pub fn standard() -> Self {
+        use Card::*;
+        Ruleset::new(
+            vec![
+                Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace,
+            ],
+            None,
+            5,
+        )
+    }
+
+    /// Part 2 rules, where `J` is a Joker: wild, and the weakest individual card.
+    pub fn jokers() -> Self {
+        use Card::*;
+        Ruleset::new(
+            vec![
+                Jack, Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Queen, King, Ace,
+            ],
+            Some(Jack),
+            5,
+        )
+    }
+
+    pub fn wild(&self) -> Option<Card> {
+        self.wild
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    pub fn strength(&self, card: Card) -> usize {
+        match self.order.iter().position(|c| *c == card) {
+            Some(strength) => strength,
+            None => panic!("card {card} is not part of the ruleset"),
+        }
+    }
+
+    pub fn strongest(&self) -> Card {
+        *self.order.last().unwrap()
+    }
+}
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone, Copy, Hash)]
+pub enum HandRank {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandRank {
+    /// Rank a hand from the counts of each distinct card, sorted largest first.
+    pub fn from_counts(counts: &[usize]) -> Self {
+        let first = counts.first().copied().unwrap_or(0);
+        let second = counts.get(1).copied().unwrap_or(0);
+
+        match (first, second) {
+            (5.., _) => HandRank::FiveOfAKind,
+            (4, _) => HandRank::FourOfAKind,
+            (3, 2..) => HandRank::FullHouse,
+            (3, _) => HandRank::ThreeOfAKind,
+            (2, 2..) => HandRank::TwoPair,
+            (2, _) => HandRank::OnePair,
+            _ => HandRank::HighCard,
+        }
+    }
+}
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone, Hash)]
+pub struct Hand {
+    // Fields must stay in this order to use "natrual" sort order
+    rank: HandRank,
+    strengths: Vec<usize>,
+    cards: Vec<Card>,
+    wild: Option<Card>,
+    substitute: Option<Card>,
+    pub bid: u32,
+}
+
+impl Hand {
+    pub fn new(cards: Vec<Card>, bid: u32, ruleset: &Ruleset) -> Hand {
+        if cards.len() != ruleset.hand_size() {
+            panic!("hand must be {} cards long", ruleset.hand_size());
+        }
+
+        let mut map: HashMap<Card, usize> = HashMap::new();
+        let mut wilds: usize = 0;
+        for card in cards.iter() {
+            match ruleset.wild() == Some(*card) {
+                true => wilds += 1,
+                false => *map.entry(*card).or_insert(0) += 1,
+            }
+        }
+
+        // Wild cards are always best spent on the most common card, preferring
+        // the strongest on a tie so the explanation is deterministic.
+        let mut counts: Vec<(Card, usize)> = map.into_iter().collect();
+        counts.sort_by(|(lhs_card, lhs), (rhs_card, rhs)| {
+            rhs.cmp(lhs).then(
+                ruleset
+                    .strength(*rhs_card)
+                    .cmp(&ruleset.strength(*lhs_card)),
+            )
+        });
+
+        let (wild, substitute) = match wilds {
+            0 => (None, None),
+            _ => (
+                ruleset.wild(),
+                Some(
+                    counts
+                        .first()
+                        .map_or(ruleset.strongest(), |(card, _)| *card),
+                ),
+            ),
+        };
+
+        let mut sizes: Vec<usize> = counts.iter().map(|(_, count)| *count).collect();
+        match sizes.first_mut() {
+            Some(size) => *size += wilds,
+            None => sizes.push(wilds),
+        }
+
+        Hand {
+            rank: HandRank::from_counts(&sizes),
+            strengths: cards.iter().map(|card| ruleset.strength(*card)).collect(),
+            cards,
+            wild,
+            substitute,
+            bid,
+        }
+    }
+
+    pub fn parse(line: &str, ruleset: &Ruleset) -> Hand {
+        let parts: Vec<_> = line.split_whitespace().collect();
+
+        let card_str = parts[0];
+        let bid = match parts[1].parse::<u32>() {
+            Ok(bid) => bid,
+            Err(e) => panic!("bid is not a number: {e}"),
+        };
+
+        let cards: Vec<Card> = card_str
+            .chars()
+            .map(|char| match Card::from_str(char.to_string().as_str()) {
+                Ok(card) => card,
+                Err(error) => panic!("invalid card {char}: {error}"),
+            })
+            .collect();
+
+        Hand::new(cards, bid, ruleset)
+    }
+
+    pub fn rank(&self) -> HandRank {
+        self.rank
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Explain the rank of this hand, including the best substitution for any wild cards.
+    pub fn explain(&self) -> Explanation {
+        let substitution = self.wild.zip(self.substitute);
+        let best: Vec<Card> = self
+            .cards
+            .iter()
+            .map(|card| match substitution {
+                Some((wild, substitute)) if *card == wild => substitute,
+                _ => *card,
+            })
+            .collect();
+
+        Explanation {
+            cards: self.cards.clone(),
+            best,
+            rank: self.rank,
+            substitution,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Explanation {
+    pub cards: Vec<Card>,
+    pub best: Vec<Card>,
+    pub rank: HandRank,
+    /// The wild card and the card it stands in for
+    pub substitution: Option<(Card, Card)>,
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cards: String = self.cards.iter().map(|card| card.to_string()).collect();
+        let best: String = self.best.iter().map(|card| card.to_string()).collect();
+
+        match self.substitution {
+            Some((wild, substitute)) => write!(
+                f,
+                "{cards} is {:?}, playing {wild} as {substitute} for {best}",
+                self.rank
+            ),
+            None => write!(f, "{cards} is {:?}", self.rank),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Card, Hand, HandRank, Ruleset};
+
+    #[test]
+    fn test_hand() {
+        let hand = Hand::parse("AATQ3 2689", &Ruleset::standard());
+
+        assert_eq!(hand.bid, 2689);
+        assert_eq!(hand.rank, HandRank::OnePair);
+        assert_eq!(
+            hand.cards,
+            [Card::Ace, Card::Ace, Card::Ten, Card::Queen, Card::Three]
+        );
+    }
+
+    #[test]
+    fn test_card_strength() {
+        let standard = Ruleset::standard();
+        assert!(standard.strength(Card::Ace) > standard.strength(Card::Nine));
+        assert!(standard.strength(Card::Three) < standard.strength(Card::Queen));
+        assert!(standard.strength(Card::Jack) > standard.strength(Card::Two));
+
+        let jokers = Ruleset::jokers();
+        assert!(jokers.strength(Card::Jack) < jokers.strength(Card::Two));
+    }
+
+    #[test]
+    fn test_wild_ranks() {
+        let jokers = Ruleset::jokers();
+        let rank = |cards: &str| Hand::parse(&format!("{cards} 1"), &jokers).rank();
+
+        assert_eq!(rank("JJJJJ"), HandRank::FiveOfAKind);
+        assert_eq!(rank("JJJJ2"), HandRank::FiveOfAKind);
+        assert_eq!(rank("KTJJT"), HandRank::FourOfAKind);
+        assert_eq!(rank("2233J"), HandRank::FullHouse);
+        assert_eq!(rank("234JJ"), HandRank::ThreeOfAKind);
+        assert_eq!(rank("2345J"), HandRank::OnePair);
+        assert_eq!(rank("23456"), HandRank::HighCard);
+    }
+
+    #[test]
+    fn test_explain() {
+        let jokers = Ruleset::jokers();
+
+        let hand = Hand::parse("KTJJT 220", &jokers);
+        assert_eq!(
+            hand.explain().to_string(),
+            "KTJJT is FourOfAKind, playing J as T for KTTTT"
+        );
+
+        let hand = Hand::parse("JJJJJ 1", &jokers);
+        assert_eq!(hand.explain().substitution, Some((Card::Jack, Card::Ace)));
+
+        let hand = Hand::parse("KTJJT 220", &Ruleset::standard());
+        assert_eq!(hand.explain().to_string(), "KTJJT is TwoPair");
+    }
+}
@@ -1,10 +1,14 @@
-pub mod cardsv2;
+pub mod cards;
 
-use cardsv2::Hand;
+use cards::{Hand, Ruleset};
 use std::{env, fs::read_to_string};
 
 fn solve(lines: Vec<String>) -> u32 {
-    let mut hands: Vec<Hand> = lines.iter().map(Hand::from).collect();
+    let ruleset = Ruleset::jokers();
+    let mut hands: Vec<Hand> = lines
+        .iter()
+        .map(|line| Hand::parse(line, &ruleset))
+        .collect();
     hands.sort();
 
     let mut total: u32 = 0;
@@ -0,0 +1,217 @@
+use crate::maps::{Node, Part2Node, Route};
+use std::collections::HashMap;
+
+/// How a ghost walks once its (node, instruction index) state starts repeating.
+///
+/// The state after `offset` steps is first seen again after `offset + length` steps,
+/// so a Z-hit at step `s >= offset` repeats at `s + k * length` forever after.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    pub offset: u64,
+    pub length: u64,
+    /// Z-hits before the cycle starts, which only ever happen once
+    pub transient_hits: Vec<u64>,
+    /// Z-hits within the first pass of the cycle
+    pub cycle_hits: Vec<u64>,
+}
+
+impl GhostCycle {
+    pub fn analyze(
+        start: &Route<Part2Node>,
+        instrs: &[u8],
+        map: &HashMap<Part2Node, Route<Part2Node>>,
+    ) -> Self {
+        let mut seen: HashMap<(Part2Node, usize), u64> = HashMap::new();
+        let mut hits: Vec<u64> = Vec::new();
+
+        let mut route = start;
+        let mut step: u64 = 0;
+        let mut pos: usize = 0;
+
+        let offset = loop {
+            if let Some(first_seen) = seen.insert((route.node, pos), step) {
+                break first_seen;
+            }
+
+            if route.node.is_end() {
+                hits.push(step);
+            }
+
+            route = match instrs[pos] {
+                b'R' => map.get(&route.right).unwrap(),
+                b'L' => map.get(&route.left).unwrap(),
+                i => panic!("Invalid instruction found: {i}"),
+            };
+
+            step += 1;
+            pos = (pos + 1) % instrs.len();
+        };
+
+        let (transient_hits, cycle_hits) = hits.into_iter().partition(|hit| *hit < offset);
+
+        GhostCycle {
+            offset,
+            length: step - offset,
+            transient_hits,
+            cycle_hits,
+        }
+    }
+
+    pub fn first_hit(&self) -> Option<u64> {
+        self.transient_hits
+            .first()
+            .or(self.cycle_hits.first())
+            .copied()
+    }
+
+    pub fn hits_at(&self, step: u64) -> bool {
+        match step < self.offset {
+            true => self.transient_hits.contains(&step),
+            false => {
+                let wrapped = self.offset + (step - self.offset) % self.length;
+                self.cycle_hits.contains(&wrapped)
+            }
+        }
+    }
+
+    /// Whether this ghost is on a Z node at exactly the positive multiples of its first hit,
+    /// which is the case the LCM shortcut relies on.
+    fn hits_multiples_of_first(&self) -> bool {
+        match self.first_hit() {
+            Some(first)
+                if first > 0 && first >= self.offset && self.length.is_multiple_of(first) =>
+            {
+                let expected: Vec<u64> = (self.offset..self.offset + self.length)
+                    .filter(|step| *step > 0 && step.is_multiple_of(first))
+                    .collect();
+                self.transient_hits.is_empty() && self.cycle_hits == expected
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Every ghost hits Z at the multiples of its first hit, so the LCM of those is exact.
+    Lcm,
+    /// Cycle hits were combined with the Chinese Remainder Theorem.
+    Crt,
+    /// All ghosts meet at a hit before at least one of them has entered its cycle.
+    Transient,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solution {
+    pub steps: u64,
+    pub method: Method,
+}
+
+/// Find the first step at which every ghost is on a Z node, if there is one.
+pub fn solve_ghosts(cycles: &[GhostCycle]) -> Option<Solution> {
+    if cycles.is_empty() {
+        return None;
+    }
+
+    if cycles.iter().all(GhostCycle::hits_multiples_of_first) {
+        let firsts: Vec<u64> = cycles.iter().filter_map(GhostCycle::first_hit).collect();
+        return Some(Solution {
+            steps: lcm(firsts),
+            method: Method::Lcm,
+        });
+    }
+
+    // Any step before the latest cycle start is a transient hit of that ghost, so checking
+    // every transient hit covers all solutions before every ghost is cycling.
+    let latest_offset = cycles.iter().map(|cycle| cycle.offset).max().unwrap();
+    let transient = cycles
+        .iter()
+        .flat_map(|cycle| cycle.transient_hits.iter().copied())
+        .filter(|step| cycles.iter().all(|cycle| cycle.hits_at(*step)))
+        .min();
+
+    if let Some(steps) = transient.filter(|steps| *steps < latest_offset) {
+        return Some(Solution {
+            steps,
+            method: Method::Transient,
+        });
+    }
+
+    let mut congruences: Vec<(u64, u64)> = vec![(0, 1)];
+    for cycle in cycles {
+        congruences = congruences
+            .iter()
+            .flat_map(|&(residue, modulus)| {
+                cycle.cycle_hits.iter().filter_map(move |hit| {
+                    crt((residue, modulus), (hit % cycle.length, cycle.length))
+                })
+            })
+            .collect();
+    }
+
+    congruences
+        .into_iter()
+        .map(|(residue, modulus)| match residue >= latest_offset {
+            true => residue,
+            false => residue + (latest_offset - residue).div_ceil(modulus) * modulus,
+        })
+        .min()
+        .map(|steps| Solution {
+            steps,
+            method: Method::Crt,
+        })
+}
+
+/// Combine `x ≡ a (mod m)` and `x ≡ b (mod n)` into a single congruence, where `m`
+/// and `n` need not be coprime.
+fn crt((a, m): (u64, u64), (b, n): (u64, u64)) -> Option<(u64, u64)> {
+    let (g, p, _) = extended_gcd(m as i128, n as i128);
+    let diff = b as i128 - a as i128;
+    if diff % g != 0 {
+        return None;
+    }
+
+    let lcm = m as i128 / g * n as i128;
+    let x = (a as i128 + m as i128 * (diff / g * p % (n as i128 / g))).rem_euclid(lcm);
+
+    Some((x as u64, lcm as u64))
+}
+
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    match b == 0 {
+        true => (a, 1, 0),
+        false => {
+            let (g, x, y) = extended_gcd(b, a % b);
+            (g, y, x - (a / b) * y)
+        }
+    }
+}
+
+// Reference: https://github.com/TheAlgorithms/Rust/blob/master/src/math/lcm_of_n_numbers.rs
+pub fn lcm(nums: Vec<u64>) -> u64 {
+    if nums.len() == 1 {
+        return nums[0];
+    }
+    let a = nums[0];
+    let b = lcm(nums.iter().cloned().skip(1).collect());
+    a * b / gcd(a, b)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b == 0 {
+        true => a,
+        false => gcd(b, a % b),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::crt;
+
+    #[test]
+    fn crt_non_coprime() {
+        assert_eq!(crt((1, 2), (2, 3)), Some((5, 6)));
+        assert_eq!(crt((0, 4), (2, 6)), Some((8, 12)));
+        assert_eq!(crt((1, 4), (2, 6)), None);
+    }
+}
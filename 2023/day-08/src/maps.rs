@@ -76,7 +76,7 @@ impl<T: Node> Route<T> {
 impl<T: Node> From<&String> for Route<T> {
     // ZZZ = (ZZZ, ZZZ)"
     fn from(value: &String) -> Self {
-        let mut node = value[..3].chars().into_iter();
+        let mut node = value[..3].chars();
         let mut left = value[7..10].chars();
         let mut right = value[12..15].chars();
         Route::new(
@@ -130,11 +130,11 @@ mod test {
 
         let start: Route<Part2Node> = Route::from(rows[0].clone());
 
-        assert_eq!(start.node.is_start(), true);
-        assert_eq!(start.node.is_end(), false);
+        assert!(start.node.is_start());
+        assert!(!start.node.is_end());
 
         let end: Route<Part2Node> = Route::from(rows[1].clone());
-        assert_eq!(end.node.is_start(), false);
-        assert_eq!(end.node.is_end(), true);
+        assert!(!end.node.is_start());
+        assert!(end.node.is_end());
     }
 }
@@ -3,16 +3,13 @@ use maps::{Node, Part1Node, Route, START_NODE_PART_1};
 use std::{collections::HashMap, env, fs::read_to_string};
 
 fn parse(lines: Vec<String>) -> (String, HashMap<Part1Node, Route<Part1Node>>) {
-    let instructions = lines.get(0).unwrap().trim();
+    let instructions = lines.first().unwrap().trim();
     let mut map: HashMap<Part1Node, Route<Part1Node>> = HashMap::new();
 
     for line in lines.iter().skip(2) {
         let route: Route<Part1Node> = Route::from(line);
-        match map.insert(route.node.clone(), route) {
-            Some(original_route) => {
-                println!("WARNING: replaced node {:?}", original_route.node);
-            }
-            None => {}
+        if let Some(original_route) = map.insert(route.node, route) {
+            println!("WARNING: replaced node {:?}", original_route.node);
         }
     }
     (instructions.to_owned(), map)
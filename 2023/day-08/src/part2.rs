@@ -1,4 +1,6 @@
+pub mod ghosts;
 pub mod maps;
+use ghosts::{solve_ghosts, GhostCycle, Solution};
 use maps::{Node, Part2Node, Route};
 use std::{collections::HashMap, env, fs::read_to_string};
 
@@ -9,7 +11,7 @@ fn parse(
     Vec<Route<Part2Node>>,
     HashMap<Part2Node, Route<Part2Node>>,
 ) {
-    let instructions = lines.get(0).unwrap().trim();
+    let instructions = lines.first().unwrap().trim();
     let mut starts: Vec<Route<Part2Node>> = Vec::new();
     let mut map: HashMap<Part2Node, Route<Part2Node>> = HashMap::new();
 
@@ -20,73 +22,24 @@ fn parse(
             starts.push(route.clone());
         }
 
-        match map.insert(route.node, route) {
-            Some(original_route) => {
-                println!("WARNING: replaced node {:?}", original_route.node);
-            }
-            None => {}
+        if let Some(original_route) = map.insert(route.node, route) {
+            println!("WARNING: replaced node {:?}", original_route.node);
         }
     }
 
     (instructions.to_owned(), starts, map)
 }
 
-fn calc_steps(
-    start: Route<Part2Node>,
-    instrs: &[u8],
-    map: &HashMap<Part2Node, Route<Part2Node>>,
-) -> u64 {
-    let mut steps: u64 = 0;
-    let mut pos: usize = 0;
-
-    let mut route = &start;
-    while !route.node.is_end() {
-        if pos >= instrs.len() {
-            pos = 0;
-        }
-
-        let instruction = instrs[pos];
-
-        route = match instruction {
-            b'R' => map.get(&route.right).unwrap(),
-            b'L' => map.get(&route.left).unwrap(),
-            i => panic!("Invalid instruction found: {i}"),
-        };
-
-        steps += 1;
-        pos += 1;
-    }
-
-    steps
-}
-
-// Reference: https://github.com/TheAlgorithms/Rust/blob/master/src/math/lcm_of_n_numbers.rs
-pub fn lcm(nums: Vec<u64>) -> u64 {
-    if nums.len() == 1 {
-        return nums[0];
-    }
-    let a = nums[0];
-    let b = lcm(nums.iter().cloned().skip(1).collect());
-    a * b / gcd(a, b)
-}
-
-fn gcd(a: u64, b: u64) -> u64 {
-    match b == 0 {
-        true => a,
-        false => gcd(b, a % b),
-    }
-}
-
-fn solve(lines: Vec<String>) -> u64 {
+fn analyze(lines: Vec<String>) -> Option<Solution> {
     let (instructions, starts, map) = parse(lines);
     let instrs: &[u8] = instructions.as_bytes();
 
-    let steps: Vec<_> = starts
+    let cycles: Vec<GhostCycle> = starts
         .iter()
-        .map(|start| calc_steps(start.to_owned(), instrs, &map))
+        .map(|start| GhostCycle::analyze(start, instrs, &map))
         .collect();
 
-    lcm(steps)
+    solve_ghosts(&cycles)
 }
 
 fn read_lines(filename: &str) -> Vec<String> {
@@ -102,18 +55,23 @@ fn main() {
 
     match args.len() {
         0..=1 => println!("Pass in filename to solve"),
-        _ => println!(
-            "Solution for {} is {}",
-            args[1].clone(),
-            solve(read_lines(&args[1].clone()))
-        ),
+        _ => match analyze(read_lines(&args[1].clone())) {
+            Some(solution) => println!(
+                "Solution for {} is {} (using {:?})",
+                args[1].clone(),
+                solution.steps,
+                solution.method
+            ),
+            None => println!("No solution for {}", args[1].clone()),
+        },
     }
 }
 
 #[cfg(test)]
 mod test {
 
-    use super::solve;
+    use super::analyze;
+    use crate::ghosts::{Method, Solution};
 
     #[test]
     fn solve_example() {
@@ -132,6 +90,99 @@ mod test {
         .map(String::from)
         .to_vec();
 
-        assert_eq!(solve(rows), 6);
+        assert_eq!(analyze(rows).map(|solution| solution.steps), Some(6));
+    }
+
+    #[test]
+    fn example_uses_lcm() {
+        let rows = [
+            "LR",
+            "",
+            "11A = (11B, XXX)",
+            "11B = (XXX, 11Z)",
+            "11Z = (11B, XXX)",
+            "22A = (22B, XXX)",
+            "22B = (22C, 22C)",
+            "22C = (22Z, 22Z)",
+            "22Z = (22B, 22B)",
+            "XXX = (XXX, XXX)",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(
+            analyze(rows),
+            Some(Solution {
+                steps: 6,
+                method: Method::Lcm
+            })
+        );
+    }
+
+    #[test]
+    fn offset_cycles_use_crt() {
+        // 11Z is hit on odd steps, 22Z on steps 2, 5, 8, ... so the LCM of first hits (2) is wrong
+        let rows = [
+            "L",
+            "",
+            "11A = (11Z, 11Z)",
+            "11Z = (11B, 11B)",
+            "11B = (11Z, 11Z)",
+            "22A = (22B, 22B)",
+            "22B = (22Z, 22Z)",
+            "22Z = (22C, 22C)",
+            "22C = (22B, 22B)",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(
+            analyze(rows),
+            Some(Solution {
+                steps: 5,
+                method: Method::Crt
+            })
+        );
+    }
+
+    #[test]
+    fn transient_hit() {
+        let rows = [
+            "L",
+            "",
+            "11A = (11Z, 11Z)",
+            "11Z = (11B, 11B)",
+            "11B = (11B, 11B)",
+            "22A = (22Z, 22Z)",
+            "22Z = (22Z, 22Z)",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(
+            analyze(rows),
+            Some(Solution {
+                steps: 1,
+                method: Method::Transient
+            })
+        );
+    }
+
+    #[test]
+    fn never_aligned() {
+        let rows = [
+            "L",
+            "",
+            "11A = (11Z, 11Z)",
+            "11Z = (11B, 11B)",
+            "11B = (11Z, 11Z)",
+            "22A = (22B, 22B)",
+            "22B = (22Z, 22Z)",
+            "22Z = (22B, 22B)",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(analyze(rows), None);
     }
 }
@@ -1,6 +1,11 @@
+use std::fmt::Display;
+
+/// A sequence of values, along with the polynomial fitted through them once when the
+/// history is built.
 #[derive(Debug)]
 pub struct History {
     values: Vec<i64>,
+    polynomial: Result<Polynomial, HistoryError>,
 }
 
 impl History {
@@ -8,33 +13,118 @@ impl History {
         &self.values
     }
 
-    pub fn iter(&self) -> HistoryIterator {
+    pub fn iter(&self) -> HistoryIterator<'_> {
         HistoryIterator {
             history: self,
             index: 0,
         }
     }
 
-    pub fn next_value(&self) -> i64 {
-        find_next_value(self.values())
+    pub fn new(values: Vec<i64>) -> Self {
+        let polynomial = fit(&values);
+        History { values, polynomial }
+    }
+
+    /// The minimal-degree polynomial through the values, fitted when the history was built.
+    pub fn fit(&self) -> Result<&Polynomial, HistoryError> {
+        self.polynomial.as_ref().map_err(Clone::clone)
     }
 
-    pub fn prev_value(&self) -> i64 {
-        let mut reverse = self.values().clone();
-        reverse.reverse();
+    /// Value `steps` positions after the last value, or before the first when negative.
+    ///
+    /// Uses the cached fit, so this is O(degree) rather than refitting the values.
+    pub fn extrapolate(&self, steps: i64) -> Result<i64, HistoryError> {
+        let polynomial = self.fit()?;
+
+        match steps >= 0 {
+            true => Ok(polynomial.evaluate(self.values.len() as i64 - 1 + steps)),
+            false => Ok(polynomial.evaluate(steps)),
+        }
+    }
 
-        find_next_value(reverse.as_ref())
+    pub fn degree(&self) -> Result<Option<usize>, HistoryError> {
+        Ok(self.fit()?.degree())
+    }
+
+    pub fn next_value(&self) -> Result<i64, HistoryError> {
+        self.extrapolate(1)
+    }
+
+    pub fn prev_value(&self) -> Result<i64, HistoryError> {
+        self.extrapolate(-1)
     }
 }
 
-fn find_next_value(values: &Vec<i64>) -> i64 {
-    let last = values.last().unwrap().to_owned();
-    let windows = values.windows(2).into_iter();
-    let seq: Vec<i64> = windows.map(|window| window[1] - window[0]).collect();
+/// Fit the minimal-degree polynomial through `values` using Newton forward differences.
+fn fit(values: &[i64]) -> Result<Polynomial, HistoryError> {
+    if values.is_empty() {
+        return Err(HistoryError::Empty);
+    }
 
-    match seq.iter().all(|v| *v == 0) {
-        true => last,
-        false => last + find_next_value(&seq),
+    let mut leading: Vec<i64> = Vec::new();
+    let mut seq: Vec<i64> = values.to_vec();
+
+    while !seq.is_empty() {
+        if seq.iter().all(|v| *v == 0) {
+            return Ok(Polynomial { leading });
+        }
+
+        leading.push(seq[0]);
+        seq = seq.windows(2).map(|window| window[1] - window[0]).collect();
+    }
+
+    Err(HistoryError::NotPolynomial { len: values.len() })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    Empty,
+    /// The differences never reached all zeros within the sequence length
+    NotPolynomial {
+        len: usize,
+    },
+}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryError::Empty => write!(f, "history has no values"),
+            HistoryError::NotPolynomial { len } => write!(
+                f,
+                "differences never reach zero within {len} values, so no polynomial fits"
+            ),
+        }
+    }
+}
+
+/// Polynomial in Newton forward form, `f(x) = Σ leading[j] * C(x, j)`, where
+/// `leading[j]` is the first value of the j-th difference sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    leading: Vec<i64>,
+}
+
+impl Polynomial {
+    /// Degree of the polynomial, or `None` when it is identically zero.
+    pub fn degree(&self) -> Option<usize> {
+        self.leading.len().checked_sub(1)
+    }
+
+    /// Evaluate at index `x` of the history in O(degree), where negative `x` is before the first value.
+    pub fn evaluate(&self, x: i64) -> i64 {
+        let x = x as i128;
+        let mut binomial: i128 = 1;
+        let mut total: i128 = 0;
+
+        for (j, leading) in self.leading.iter().enumerate() {
+            if j > 0 {
+                // C(x, j) = C(x, j - 1) * (x - j + 1) / j, which always divides exactly
+                binomial = binomial * (x - j as i128 + 1) / j as i128;
+            }
+            total += *leading as i128 * binomial;
+        }
+
+        total as i64
     }
 }
 
@@ -61,11 +151,10 @@ impl From<&String> for History {
     fn from(values_str: &String) -> Self {
         let values: Vec<i64> = values_str
             .split_whitespace()
-            .into_iter()
             .map(|value| value.parse::<i64>().unwrap())
             .collect();
 
-        History { values }
+        History::new(values)
     }
 }
 
@@ -79,7 +168,7 @@ impl From<&String> for History {
 mod test {
     use rstest::rstest;
 
-    use super::History;
+    use super::{History, HistoryError};
 
     #[test]
     fn parse() {
@@ -96,7 +185,7 @@ mod test {
     #[case("10 13 16 21 30 45", 68)]
     fn next_value(#[case] values: &str, #[case] expected: i64) {
         let history = History::from(&values.to_string());
-        assert_eq!(history.next_value(), expected);
+        assert_eq!(history.next_value(), Ok(expected));
     }
 
     #[rstest]
@@ -105,6 +194,37 @@ mod test {
     #[case("10 13 16 21 30 45", 5)]
     fn prev_value(#[case] values: &str, #[case] expected: i64) {
         let history = History::from(&values.to_string());
-        assert_eq!(history.prev_value(), expected);
+        assert_eq!(history.prev_value(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("0 3 6 9 12 15", 3, 24)]
+    #[case("1 3 6 10 15 21", 2, 36)]
+    #[case("1 3 6 10 15 21", -3, 1)]
+    #[case("10 13 16 21 30 45", 0, 45)]
+    #[case("5 5 5", -10, 5)]
+    fn extrapolate(#[case] values: &str, #[case] steps: i64, #[case] expected: i64) {
+        let history = History::from(&values.to_string());
+        assert_eq!(history.extrapolate(steps), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("0 0 0", None)]
+    #[case("5 5 5", Some(0))]
+    #[case("0 3 6 9 12 15", Some(1))]
+    #[case("1 3 6 10 15 21", Some(2))]
+    #[case("10 13 16 21 30 45", Some(3))]
+    fn degree(#[case] values: &str, #[case] expected: Option<usize>) {
+        let history = History::from(&values.to_string());
+        assert_eq!(history.degree(), Ok(expected));
+    }
+
+    #[test]
+    fn not_polynomial() {
+        let history = History::from(&"1 2 4 8".to_string());
+        assert_eq!(
+            history.next_value(),
+            Err(HistoryError::NotPolynomial { len: 4 })
+        );
     }
 }
@@ -13,7 +13,10 @@ fn solve(lines: Vec<String>) -> i64 {
 
     histories
         .into_iter()
-        .fold(0, |acc, history| acc + history.next_value())
+        .fold(0, |acc, history| match history.next_value() {
+            Ok(value) => acc + value,
+            Err(err) => panic!("Cannot extrapolate {:?}: {err}", history.values()),
+        })
 }
 
 fn read_lines(filename: &str) -> Vec<String> {
@@ -18,7 +18,10 @@ fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
 }
 
 fn parse(lines: Vec<String>) -> Universe {
-    let mut universe: Universe = Universe::new();
+    let num_cols = lines.first().map_or(0, |line| line.len());
+    let mut galaxies: Vec<Galaxy> = Vec::new();
+    let mut expansion_rows: Vec<usize> = Vec::new();
+    let mut expansion_cols: Vec<usize> = Vec::new();
 
     let mut matrix: Vec<Vec<char>> = Vec::new();
     let mut galaxy_id = 1;
@@ -27,12 +30,12 @@ fn parse(lines: Vec<String>) -> Universe {
         let chars: Vec<_> = line.chars().collect();
         for (c, ch) in chars.iter().enumerate() {
             if *ch == '#' {
-                universe.add_galaxy(Galaxy::new(galaxy_id, r, c));
+                galaxies.push(Galaxy::new(galaxy_id, r, c));
                 galaxy_id += 1;
             }
         }
         if line.chars().all(|ch| ch == '.') {
-            universe.add_expansion_row(r);
+            expansion_rows.push(r);
         }
 
         matrix.push(line.chars().collect());
@@ -40,39 +43,32 @@ fn parse(lines: Vec<String>) -> Universe {
 
     for (i, col) in transpose(matrix).iter().enumerate() {
         if col.clone().into_iter().all(|ch| ch == '.') {
-            universe.add_expansion_col(i);
+            expansion_cols.push(i);
         }
     }
 
+    let mut universe = Universe::new(lines.len(), num_cols, expansion_rows, expansion_cols);
+    for galaxy in galaxies {
+        universe.add_galaxy(galaxy);
+    }
+
     universe
 }
 
-fn solve(universe: &Universe, expansion_factor: usize) -> usize {
-    let mut total = 0;
-
-    let galaxies = universe.galaxies();
-
-    let mut galaxy = galaxies.get(0);
-    let mut skip = 1;
-    while galaxy.is_some() {
-        let lhs = galaxy.unwrap();
-        for rhs in galaxies.iter().skip(skip) {
-            total += universe.distance(lhs, rhs, expansion_factor);
-        }
-        galaxy = galaxies.get(skip);
-        skip += 1;
-    }
-
-    total
+fn solve(universe: &Universe, expansion_factors: &[usize]) -> Vec<usize> {
+    universe.sums_of_distances(expansion_factors)
 }
 
 fn main() {
     let universe = parse(read_lines("input.txt"));
 
-    println!("Solution for part 1 is {}", solve(&universe, 2));
-    println!("Solution for part 2 is {}", solve(&universe, 1000000));
+    let solutions = solve(&universe, &[2, 1000000]);
+
+    println!("Solution for part 1 is {}", solutions[0]);
+    println!("Solution for part 2 is {}", solutions[1]);
 }
 
+#[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 mod test {
 
@@ -100,7 +96,43 @@ mod test {
     fn solve_example(#[case] expand_factor: usize, #[case] expected: usize) {
         let universe = parse(EXAMPLE.map(String::from).to_vec());
 
-        assert_eq!(solve(&universe, expand_factor), expected);
+        assert_eq!(solve(&universe, &[expand_factor]), vec![expected]);
+    }
+
+    #[test]
+    fn multiple_factors_match_pairwise() {
+        let universe = parse(EXAMPLE.map(String::from).to_vec());
+        let factors = [2, 10, 100, 1000000];
+
+        let expected: Vec<usize> = factors
+            .iter()
+            .map(|factor| {
+                let galaxies = universe.galaxies();
+                let mut total = 0;
+                for (i, lhs) in galaxies.iter().enumerate() {
+                    for rhs in galaxies.iter().skip(i + 1) {
+                        total += universe.distance(lhs, rhs, *factor);
+                    }
+                }
+                total
+            })
+            .collect();
+
+        assert_eq!(solve(&universe, &factors), expected);
+        assert_eq!(expected[..3], [374, 1030, 8410]);
+    }
+
+    #[test]
+    fn factor_of_zero_removes_expansions() {
+        let universe = parse(EXAMPLE.map(String::from).to_vec());
+        let galaxies = universe.galaxies();
+
+        // Galaxies 1 and 2 are 1 row and 4 columns apart, crossing one expansion column
+        assert_eq!(universe.distance(&galaxies[0], &galaxies[1], 0), 4);
+        assert_eq!(universe.distance(&galaxies[0], &galaxies[1], 2), 6);
+
+        let sums = solve(&universe, &[0, 1, 2]);
+        assert_eq!(sums[0], 2 * sums[1] - sums[2]);
     }
 }
 
@@ -2,32 +2,44 @@
 pub struct Universe {
     expansion_rows: Vec<usize>,
     expansion_cols: Vec<usize>,
+    // Number of expansion rows (or columns) strictly before each index
+    rows_before: Vec<usize>,
+    cols_before: Vec<usize>,
     galaxies: Vec<Galaxy>,
 }
 
 impl Universe {
-    pub fn new() -> Self {
+    /// Build the universe from the empty rows and columns found while parsing, counting
+    /// the expansions before each index once.
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        expansion_rows: Vec<usize>,
+        expansion_cols: Vec<usize>,
+    ) -> Self {
         Universe {
-            expansion_rows: Vec::new(),
-            expansion_cols: Vec::new(),
+            rows_before: expansions_before(rows, &expansion_rows),
+            cols_before: expansions_before(cols, &expansion_cols),
+            expansion_rows,
+            expansion_cols,
             galaxies: Vec::new(),
         }
     }
 
     pub fn is_expansion_row(&self, r: usize) -> bool {
-        self.expansion_rows.contains(&r)
+        self.rows_before[r + 1] > self.rows_before[r]
     }
 
     pub fn is_expansion_col(&self, c: usize) -> bool {
-        self.expansion_cols.contains(&c)
+        self.cols_before[c + 1] > self.cols_before[c]
     }
 
-    pub fn add_expansion_row(&mut self, r: usize) {
-        self.expansion_rows.push(r)
+    pub fn expansion_rows(&self) -> &[usize] {
+        &self.expansion_rows
     }
 
-    pub fn add_expansion_col(&mut self, c: usize) {
-        self.expansion_cols.push(c)
+    pub fn expansion_cols(&self) -> &[usize] {
+        &self.expansion_cols
     }
 
     pub fn add_galaxy(&mut self, galaxy: Galaxy) {
@@ -37,31 +49,83 @@ impl Universe {
         &self.galaxies
     }
 
+    /// Distance between two galaxies in O(1), where each expansion row or column
+    /// counts as `expansion_factor`.
     pub fn distance(&self, lhs: &Galaxy, rhs: &Galaxy, expansion_factor: usize) -> usize {
-        let mut rows = vec![lhs.row(), rhs.row()];
-        rows.sort();
-
-        let mut cols = vec![lhs.col(), rhs.col()];
-        cols.sort();
-
-        let mut row_diff = 0;
-        for r in (rows[0]..rows[1]).into_iter() {
-            row_diff += match self.is_expansion_row(r) {
-                true => expansion_factor,
-                false => 1,
-            }
-        }
+        let rows = lhs.row().abs_diff(rhs.row());
+        let expanded_rows = self.rows_before[lhs.row()].abs_diff(self.rows_before[rhs.row()]);
 
-        let mut col_diff = 0;
-        for c in (cols[0]..cols[1]).into_iter() {
-            col_diff += match self.is_expansion_col(c) {
-                true => expansion_factor,
-                false => 1,
-            }
-        }
+        let cols = lhs.col().abs_diff(rhs.col());
+        let expanded_cols = self.cols_before[lhs.col()].abs_diff(self.cols_before[rhs.col()]);
+
+        expand(rows + cols, expanded_rows + expanded_cols, expansion_factor)
+    }
+
+    /// Sum of the distances between every pair of galaxies, in O(n log n).
+    pub fn sum_of_distances(&self, expansion_factor: usize) -> usize {
+        self.sums_of_distances(&[expansion_factor])[0]
+    }
+
+    /// Sum of the distances between every pair of galaxies for each expansion factor.
+    ///
+    /// Expansion never reorders galaxies along an axis, so the pairwise sum splits into
+    /// the distance across regular rows and columns plus `expansion_factor` times the
+    /// number of expansion rows and columns crossed, both of which are computed once.
+    pub fn sums_of_distances(&self, expansion_factors: &[usize]) -> Vec<usize> {
+        let (rows, expanded_rows) = axis_sums(
+            self.galaxies
+                .iter()
+                .map(|galaxy| (galaxy.row(), self.rows_before[galaxy.row()])),
+        );
+        let (cols, expanded_cols) = axis_sums(
+            self.galaxies
+                .iter()
+                .map(|galaxy| (galaxy.col(), self.cols_before[galaxy.col()])),
+        );
+
+        expansion_factors
+            .iter()
+            .map(|factor| expand(rows + cols, expanded_rows + expanded_cols, *factor))
+            .collect()
+    }
+}
 
-        row_diff + col_diff
+// Distance where `expanded` of the `span` rows and columns crossed count as `factor` each
+fn expand(span: usize, expanded: usize, factor: usize) -> usize {
+    (span - expanded) + expanded * factor
+}
+
+// Number of expansions strictly before each index, up to and including `len`
+fn expansions_before(len: usize, expansions: &[usize]) -> Vec<usize> {
+    let mut is_expansion = vec![false; len];
+    for index in expansions {
+        is_expansion[*index] = true;
+    }
+
+    let mut before = vec![0; len + 1];
+    for (index, expansion) in is_expansion.into_iter().enumerate() {
+        before[index + 1] = before[index] + expansion as usize;
+    }
+
+    before
+}
+
+/// Sum of pairwise differences of both the positions and the expansion counts along one axis.
+fn axis_sums(coordinates: impl Iterator<Item = (usize, usize)>) -> (usize, usize) {
+    let mut coordinates: Vec<(usize, usize)> = coordinates.collect();
+    coordinates.sort();
+
+    // Once sorted, the i-th value is subtracted from every later one and added to every earlier one
+    let n = coordinates.len();
+    let mut positions: i64 = 0;
+    let mut expanded: i64 = 0;
+    for (i, (position, before)) in coordinates.into_iter().enumerate() {
+        let weight = 2 * i as i64 + 1 - n as i64;
+        positions += weight * position as i64;
+        expanded += weight * before as i64;
     }
+
+    (positions as usize, expanded as usize)
 }
 
 #[derive(Debug, Eq, PartialEq)]
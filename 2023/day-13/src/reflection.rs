@@ -0,0 +1,133 @@
+use Orientation::*;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+    pub fn multiply(&self, value: usize) -> usize {
+        match self {
+            Horizontal => value * 100,
+            Vertical => value,
+        }
+    }
+}
+
+/// A line of reflection, which sits between `position - 1` and `position`.
+///
+/// Horizontal lines are between rows, vertical lines are between columns.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Reflection {
+    orientation: Orientation,
+    position: usize,
+}
+
+impl Reflection {
+    pub fn new(orientation: Orientation, position: usize) -> Self {
+        Reflection {
+            orientation,
+            position,
+        }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn summarize(&self) -> usize {
+        self.orientation.multiply(self.position)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    matrix: Vec<Vec<char>>,
+}
+
+impl Pattern {
+    pub fn rows(&self) -> usize {
+        self.matrix.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.matrix.first().map_or(0, |row| row.len())
+    }
+
+    /// Number of lines running perpendicular to a reflection of the given orientation.
+    fn lines(&self, orientation: Orientation) -> usize {
+        match orientation {
+            Horizontal => self.rows(),
+            Vertical => self.cols(),
+        }
+    }
+
+    /// Character at position `i` along `line`, reading rows for horizontal
+    /// reflections and columns for vertical ones.
+    fn get(&self, orientation: Orientation, line: usize, i: usize) -> char {
+        match orientation {
+            Horizontal => self.matrix[line][i],
+            Vertical => self.matrix[i][line],
+        }
+    }
+
+    fn line_len(&self, orientation: Orientation) -> usize {
+        match orientation {
+            Horizontal => self.cols(),
+            Vertical => self.rows(),
+        }
+    }
+
+    /// Characters that differ between each pair of lines folded onto each other at
+    /// `position`, starting with the pair either side of the reflection.
+    pub fn differences(&self, orientation: Orientation, position: usize) -> Vec<usize> {
+        let lines = self.lines(orientation);
+        let len = self.line_len(orientation);
+
+        (0..position.min(lines.saturating_sub(position)))
+            .map(|offset| {
+                let before = position - 1 - offset;
+                let after = position + offset;
+                (0..len)
+                    .filter(|&i| {
+                        self.get(orientation, before, i) != self.get(orientation, after, i)
+                    })
+                    .count()
+            })
+            .collect()
+    }
+
+    /// Every reflection, in either orientation, with exactly `smudges` differences.
+    pub fn reflections(&self, smudges: usize) -> Vec<Reflection> {
+        [Horizontal, Vertical]
+            .into_iter()
+            .flat_map(|orientation| {
+                (1..self.lines(orientation))
+                    .filter(move |&position| {
+                        self.differences(orientation, position)
+                            .iter()
+                            .sum::<usize>()
+                            == smudges
+                    })
+                    .map(move |position| Reflection::new(orientation, position))
+            })
+            .collect()
+    }
+
+    pub fn reflection(&self, smudges: usize) -> Option<Reflection> {
+        self.reflections(smudges).into_iter().next()
+    }
+}
+
+impl From<Vec<String>> for Pattern {
+    fn from(lines: Vec<String>) -> Self {
+        Pattern {
+            matrix: lines.iter().map(|line| line.chars().collect()).collect(),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::{fmt::Display, str::FromStr};
+
+pub const CAPACITY: usize = 256;
+
+/// Holiday ASCII String Helper algorithm.
+pub fn hash(value: &str) -> usize {
+    let mut result: usize = 0;
+    for ch in value.chars() {
+        let code = ch as usize;
+
+        // Increase the current value by the ASCII code you just determined.
+        result += code;
+
+        // Set the current value to itself multiplied by 17.
+        result *= 17;
+
+        // Set the current value to the remainder of dividing itself by 256.
+        result %= CAPACITY;
+    }
+
+    result
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Step {
+    Insert { label: String, focal: usize },
+    Remove { label: String },
+}
+
+impl Step {
+    pub fn label(&self) -> &str {
+        match self {
+            Step::Insert { label, .. } | Step::Remove { label } => label,
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(label) = value.strip_suffix('-') {
+            return Ok(Step::Remove {
+                label: label.to_string(),
+            });
+        }
+
+        match value.split_once('=') {
+            Some((label, focal)) => {
+                let focal = focal
+                    .parse::<usize>()
+                    .map_err(|err| format!("Invalid focal length in '{value}': {err}"))?;
+
+                Ok(Step::Insert {
+                    label: label.to_string(),
+                    focal,
+                })
+            }
+            None => Err(format!("Invalid step '{value}'")),
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Insert { label, focal } => write!(f, "{label}={focal}"),
+            Step::Remove { label } => write!(f, "{label}-"),
+        }
+    }
+}
+
+/// Holiday ASCII String Helper Manual Arrangement Procedure: an ordered-bucket
+/// map, where each box keeps its entries in insertion order.
+#[derive(Debug, Clone)]
+pub struct LensBoxes<V> {
+    boxes: Vec<Vec<(String, V)>>,
+}
+
+impl<V> LensBoxes<V> {
+    pub fn new() -> Self {
+        let mut boxes: Vec<Vec<(String, V)>> = Vec::with_capacity(CAPACITY);
+        (0..CAPACITY).for_each(|_| boxes.push(Vec::new()));
+
+        LensBoxes { boxes }
+    }
+
+    /// Insert `value` under `label`, replacing an existing entry in place so
+    /// it keeps its position within the box.
+    pub fn insert(&mut self, label: &str, value: V) {
+        let entries = &mut self.boxes[hash(label)];
+        match entries.iter_mut().find(|(key, _)| key == label) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((label.to_string(), value)),
+        }
+    }
+
+    pub fn remove(&mut self, label: &str) -> Option<V> {
+        let entries = &mut self.boxes[hash(label)];
+        let pos = entries.iter().position(|(key, _)| key == label)?;
+
+        Some(entries.remove(pos).1)
+    }
+
+    pub fn get(&self, label: &str) -> Option<&V> {
+        self.boxes[hash(label)]
+            .iter()
+            .find(|(key, _)| key == label)
+            .map(|(_, value)| value)
+    }
+
+    pub fn entries(&self, box_num: usize) -> &[(String, V)] {
+        &self.boxes[box_num]
+    }
+}
+
+impl<V> Default for LensBoxes<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LensBoxes<usize> {
+    pub fn apply(&mut self, step: &Step) {
+        match step {
+            Step::Insert { label, focal } => self.insert(label, *focal),
+            Step::Remove { label } => {
+                self.remove(label);
+            }
+        }
+    }
+
+    /// Apply every step, returning the box contents after each one in the
+    /// same format as the puzzle text.
+    pub fn apply_with_trace(&mut self, steps: &[Step]) -> String {
+        let mut trace = String::new();
+
+        for step in steps {
+            self.apply(step);
+            trace.push_str(&format!("After \"{step}\":\n{self}\n"));
+        }
+
+        trace
+    }
+
+    pub fn focusing_power(&self) -> usize {
+        let mut total: usize = 0;
+        for (pos, lenses) in self.boxes.iter().enumerate() {
+            let box_num = pos + 1;
+            for (lens_pos, (_, focal)) in lenses.iter().enumerate() {
+                total += box_num * (lens_pos + 1) * focal;
+            }
+        }
+
+        total
+    }
+}
+
+impl<V: Display> Display for LensBoxes<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (pos, entries) in self.boxes.iter().enumerate() {
+            if entries.is_empty() {
+                continue;
+            }
+
+            write!(f, "Box {pos}:")?;
+            for (label, value) in entries {
+                write!(f, " [{label} {value}]")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
-use std::fs::read_to_string;
+pub mod lens_boxes;
 
-const CAPACITY: usize = 256;
+use lens_boxes::{hash, LensBoxes, Step};
+use std::fs::read_to_string;
 
 fn parse(lines: Vec<String>) -> Vec<String> {
-    match lines.get(0) {
+    match lines.first() {
         Some(line) => {
-            let steps: Vec<&str> = line.split(",").collect();
+            let steps: Vec<&str> = line.split(',').collect();
             steps.into_iter().map(String::from).collect()
         }
         None => {
@@ -14,93 +15,31 @@ fn parse(lines: Vec<String>) -> Vec<String> {
     }
 }
 
-fn hash(value: &String) -> usize {
-    let mut result: usize = 0;
-    for ch in value.chars().into_iter() {
-        let code = ch as usize;
-
-        // Increase the current value by the ASCII code you just determined.
-        result += code;
-
-        // Set the current value to itself multiplied by 17.
-        result *= 17;
-
-        // Set the current value to the remainder of dividing itself by 256.
-        result %= CAPACITY;
-    }
-
-    result
+fn parse_steps(steps: &[String]) -> Vec<Step> {
+    steps
+        .iter()
+        .map(|step| step.parse::<Step>().unwrap())
+        .collect()
 }
 
-fn solve_part1(steps: &Vec<String>) -> usize {
+fn solve_part1(steps: &[String]) -> usize {
     let mut total: usize = 0;
 
-    for step in steps.into_iter() {
-        total += hash(&step);
+    for step in steps.iter() {
+        total += hash(step);
     }
 
     total
 }
 
-struct Lens {
-    label: String,
-    focal_length: usize,
-}
-
-impl Lens {
-    pub fn get_hash(&self) -> usize {
-        hash(&self.label)
-    }
-}
-
-impl From<&String> for Lens {
-    fn from(value: &String) -> Self {
-        let splits: Vec<_> = value.split("=").collect();
-
-        let label: String = splits[0].to_string();
-        let focal_length: usize = splits[1].parse::<usize>().unwrap();
-
-        Lens {
-            label,
-            focal_length,
-        }
-    }
-}
-
-fn solve_part2(steps: &Vec<String>) -> usize {
-    let mut boxes: Vec<Vec<Lens>> = Vec::with_capacity(CAPACITY);
-    (0..CAPACITY).for_each(|_| {
-        boxes.push(Vec::new());
-    });
-
-    for step in steps {
-        match step.contains("=") {
-            true => {
-                let lens = Lens::from(step);
-                let lenses: &mut Vec<Lens> = &mut boxes[lens.get_hash()];
-                match lenses.iter_mut().find(|item| item.label == lens.label) {
-                    Some(item) => item.focal_length = lens.focal_length,
-                    None => lenses.push(lens),
-                }
-            }
-            false => {
-                let split: Vec<&str> = step.split("-").collect();
-                let label = split[0].to_string();
-                let lenses: &mut Vec<Lens> = &mut boxes[hash(&label)];
-                lenses.retain(|lens| lens.label != label)
-            }
-        }
-    }
+fn solve_part2(steps: &[String]) -> usize {
+    let mut boxes: LensBoxes<usize> = LensBoxes::new();
 
-    let mut total: usize = 0;
-    for (pos, lenses) in boxes.into_iter().enumerate() {
-        let box_num = pos + 1;
-        for (lens_pos, lens) in lenses.into_iter().enumerate() {
-            total += (box_num) * (lens_pos + 1) * lens.focal_length;
-        }
+    for step in parse_steps(steps).iter() {
+        boxes.apply(step);
     }
 
-    total
+    boxes.focusing_power()
 }
 
 fn main() {
@@ -110,10 +49,11 @@ fn main() {
     println!("Solution for part 2 is {}", solve_part2(&steps));
 }
 
+#[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 mod test {
 
-    use super::{parse, solve_part1, solve_part2};
+    use super::{parse, parse_steps, solve_part1, solve_part2, LensBoxes, Step};
 
     const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
 
@@ -128,6 +68,39 @@ mod test {
         let steps: Vec<String> = parse(vec![EXAMPLE.to_string()]);
         assert_eq!(solve_part2(&steps), 145);
     }
+
+    #[test]
+    fn parse_step() {
+        assert_eq!(
+            "rn=1".parse::<Step>(),
+            Ok(Step::Insert {
+                label: "rn".to_string(),
+                focal: 1
+            })
+        );
+        assert_eq!(
+            "cm-".parse::<Step>(),
+            Ok(Step::Remove {
+                label: "cm".to_string()
+            })
+        );
+        assert!("cm".parse::<Step>().is_err());
+    }
+
+    #[test]
+    fn trace_example() {
+        let steps: Vec<Step> = parse_steps(&parse(vec![EXAMPLE.to_string()]));
+        let mut boxes: LensBoxes<usize> = LensBoxes::new();
+
+        let trace = boxes.apply_with_trace(&steps[..3]);
+
+        assert_eq!(
+            trace,
+            "After \"rn=1\":\nBox 0: [rn 1]\n\n\
+             After \"cm-\":\nBox 0: [rn 1]\n\n\
+             After \"qp=3\":\nBox 0: [rn 1]\nBox 1: [qp 3]\n\n"
+        );
+    }
 }
 
 // Utilities
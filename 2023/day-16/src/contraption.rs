@@ -1,13 +1,12 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    str::FromStr,
-};
+use std::{str::FromStr, thread};
 use strum_macros::EnumString;
 use Direction::*;
 
 #[derive(Debug, Clone)]
 pub struct Contraption {
-    matrix: Vec<Vec<Tile>>,
+    tiles: Vec<TileType>,
+    rows: usize,
+    cols: usize,
 }
 
 impl Contraption {
@@ -16,88 +15,171 @@ impl Contraption {
     }
 
     pub fn rows(&self) -> usize {
-        self.matrix.len()
+        self.rows
     }
 
     pub fn cols(&self) -> usize {
-        self.matrix[0].len()
+        self.cols
     }
 
-    pub fn tile_mut(&mut self, trajectory: Trajectory) -> Option<&mut Tile> {
-        self.matrix
-            .get_mut(trajectory.row)
-            .and_then(|row| row.get_mut(trajectory.col).and_then(|tile| Some(tile)))
+    fn index(&self, row: usize, col: usize) -> Option<usize> {
+        match row < self.rows && col < self.cols {
+            true => Some(row * self.cols + col),
+            false => None,
+        }
     }
 
-    pub fn reset(&mut self) {
-        for row in self.matrix.iter_mut() {
-            for tile in row.iter_mut() {
-                tile.reset();
-            }
-        }
+    pub fn tile_type(&self, row: usize, col: usize) -> Option<TileType> {
+        self.index(row, col).map(|index| self.tiles[index])
     }
 
-    pub fn total_energized(&self) -> usize {
-        let mut total: usize = 0;
-        for row in &self.matrix {
-            for tile in row {
-                if tile.is_energized() {
-                    total += 1;
-                }
+    /// Walk a beam from `initial`, returning the set of energized tiles.
+    ///
+    /// Each tile tracks the directions it has been entered from as a bitmask,
+    /// so a beam is only followed the first time it enters a tile in a given direction.
+    pub fn walk(&self, initial: Trajectory) -> Energized {
+        let mut entered: Vec<u8> = vec![0; self.tiles.len()];
+        let mut trajectories = vec![initial];
+
+        while let Some(trajectory) = trajectories.pop() {
+            let Some(index) = self.index(trajectory.row, trajectory.col) else {
+                continue;
+            };
+
+            let bit = trajectory.dir.bit();
+            if entered[index] & bit != 0 {
+                continue;
             }
+            entered[index] |= bit;
+
+            trajectories.extend(self.tiles[index].get_next(trajectory));
+        }
+
+        let mut energized = Energized::new(self.rows, self.cols);
+        for (index, _) in entered.iter().enumerate().filter(|(_, dirs)| **dirs != 0) {
+            energized.insert(index);
         }
 
-        total
+        energized
     }
 
-    // Iterative solution to walk
-    pub fn walk(&mut self, initial: Trajectory) {
-        let mut trajectories = VecDeque::new();
-        trajectories.push_back(initial);
+    /// All trajectories entering the contraption from one of its edges, heading inwards.
+    pub fn edge_entries(&self) -> Vec<Trajectory> {
+        let mut entries: Vec<Trajectory> = Vec::with_capacity(2 * (self.rows + self.cols));
+        if self.rows == 0 || self.cols == 0 {
+            return entries;
+        }
 
-        while !trajectories.is_empty() {
-            let trajectory = trajectories.pop_front().unwrap();
-            match self.tile_mut(trajectory) {
-                None => {}
-                Some(tile) => {
-                    if tile.energize(trajectory.dir) {
-                        let next_trajectories = tile.get_next(trajectory);
+        let last_row = self.rows - 1;
+        let last_col = self.cols - 1;
 
-                        for next_trajectory in next_trajectories.into_iter() {
-                            trajectories.push_front(next_trajectory);
-                        }
-                    }
-                }
-            }
+        (0..self.rows).for_each(|row| {
+            entries.push(Trajectory::new(row, 0, East));
+            entries.push(Trajectory::new(row, last_col, West));
+        });
+
+        (0..self.cols).for_each(|col| {
+            entries.push(Trajectory::new(0, col, South));
+            entries.push(Trajectory::new(last_row, col, North));
+        });
+
+        entries
+    }
+
+    /// Walk every edge entry across the available threads, returning the entry that
+    /// energizes the most tiles along with that count, or `None` for an empty contraption.
+    pub fn best_entry(&self) -> Option<(Trajectory, usize)> {
+        let entries = self.edge_entries();
+        if entries.is_empty() {
+            return None;
         }
+
+        let num_threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = entries.len().div_ceil(num_threads);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = entries
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|&entry| (entry, self.walk(entry).count()))
+                            .max_by_key(|(_, count)| *count)
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().unwrap())
+                .max_by_key(|(_, count)| *count)
+        })
     }
 
-    pub fn _print_energized(&self) {
-        for row in &self.matrix {
-            for tile in row {
-                match tile.is_energized() {
+    pub fn _print_energized(&self, energized: &Energized) {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                match energized.contains(row, col) {
                     true => print!("#"),
                     false => print!("."),
                 }
             }
-            println!("");
+            println!();
         }
     }
 }
 
 impl From<Vec<String>> for Contraption {
     fn from(lines: Vec<String>) -> Self {
-        let mut matrix: Vec<Vec<Tile>> = Vec::with_capacity(lines.len());
+        let rows = lines.len();
+        let cols = lines.first().map_or(0, |line| line.len());
+        let mut tiles: Vec<TileType> = Vec::with_capacity(rows * cols);
 
         for line in lines {
-            let mut row: Vec<Tile> = Vec::with_capacity(line.len());
-            for char in line.chars() {
-                row.push(Tile::from(char));
-            }
-            matrix.push(row);
+            assert_eq!(line.len(), cols, "all rows must be the same width");
+            tiles.extend(line.chars().map(TileType::from_char));
+        }
+
+        Contraption { tiles, rows, cols }
+    }
+}
+
+/// Bitset of the energized tiles in a contraption, indexed row-major.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Energized {
+    cols: usize,
+    bits: Vec<u64>,
+}
+
+impl Energized {
+    fn new(rows: usize, cols: usize) -> Self {
+        Energized {
+            cols,
+            bits: vec![0; (rows * cols).div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.bits[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        if col >= self.cols {
+            return false;
         }
 
-        Contraption { matrix }
+        let index = row * self.cols + col;
+        self.bits
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    pub fn count(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
     }
 }
 
@@ -153,7 +235,7 @@ impl TileType {
 
         let result: Vec<Trajectory> = next_dirs
             .into_iter()
-            .map(|next_dir| match next_dir {
+            .filter_map(|next_dir| match next_dir {
                 North => match t.row == 0 {
                     true => None,
                     false => Some(Trajectory::new(t.row - 1, t.col, North)),
@@ -165,61 +247,12 @@ impl TileType {
                     false => Some(Trajectory::new(t.row, t.col - 1, West)),
                 },
             })
-            .filter_map(|dir: Option<Trajectory>| dir)
             .collect();
 
         result
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Tile {
-    tile_type: TileType,
-    is_energized: bool,
-    energized_from: HashMap<Direction, bool>,
-}
-
-impl Tile {
-    pub fn tile_type(&self) -> TileType {
-        self.tile_type
-    }
-
-    pub fn is_energized(&self) -> bool {
-        self.is_energized
-    }
-
-    pub fn energize(&mut self, from: Direction) -> bool {
-        self.is_energized = true;
-
-        match self.energized_from.get(&from) {
-            Some(_) => false,
-            None => {
-                self.energized_from.insert(from, true);
-                true
-            }
-        }
-    }
-
-    pub fn reset(&mut self) {
-        self.energized_from.clear();
-        self.is_energized = false;
-    }
-
-    pub fn get_next(&self, t: Trajectory) -> Vec<Trajectory> {
-        self.tile_type.get_next(t)
-    }
-}
-
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        Tile {
-            tile_type: TileType::from_char(value),
-            is_energized: false,
-            energized_from: HashMap::new(),
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 pub enum Direction {
     North,
@@ -228,7 +261,18 @@ pub enum Direction {
     West,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Direction {
+    fn bit(self) -> u8 {
+        match self {
+            North => 0b0001,
+            South => 0b0010,
+            East => 0b0100,
+            West => 0b1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct Trajectory {
     row: usize,
     col: usize,
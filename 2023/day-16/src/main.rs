@@ -3,75 +3,30 @@ pub mod contraption;
 use contraption::{Contraption, Direction, Trajectory};
 use std::fs::read_to_string;
 
-// Recursive solution to walk
-fn walk(contraption: &mut Contraption, trajectory: Trajectory) {
-    match contraption.tile_mut(trajectory) {
-        None => return,
-        Some(tile) => {
-            if tile.energize(trajectory.dir()) {
-                let next_tiles = tile.get_next(trajectory);
-                for new_trajectory in next_tiles.into_iter() {
-                    walk(contraption, new_trajectory);
-                }
-            }
-        }
-    }
-}
-
-fn solve_part1(mut contraption: Contraption) -> usize {
+fn solve_part1(contraption: &Contraption) -> usize {
     let start = Trajectory::new(0, 0, Direction::East);
 
-    walk(&mut contraption, start);
-
-    contraption.total_energized()
+    contraption.walk(start).count()
 }
 
-fn solve_part2(mut contraption: Contraption) -> usize {
-    let mut starts: Vec<Trajectory> = Vec::new();
-
-    let (num_rows, num_cols) = contraption.size();
-    let last_row = num_rows - 1;
-    let last_col = num_cols - 1;
-
-    (0..num_rows).for_each(|row| {
-        starts.push(Trajectory::new(row, 0, Direction::East));
-        starts.push(Trajectory::new(row, last_col, Direction::West));
-    });
-
-    (0..num_cols).for_each(|col| {
-        starts.push(Trajectory::new(0, col, Direction::South));
-        starts.push(Trajectory::new(last_row, col, Direction::North));
-    });
-
-    let mut result = 0;
-    for start in starts.into_iter() {
-        contraption.walk(start);
-        let total = contraption.total_energized();
-        if total > result {
-            result = total;
-        }
-
-        contraption.reset()
-    }
-
-    result
+fn solve_part2(contraption: &Contraption) -> usize {
+    contraption
+        .best_entry()
+        .map_or(0, |(_entry, energized)| energized)
 }
 
 fn main() {
     let contraption: Contraption = Contraption::from(read_lines("input.txt"));
 
-    println!(
-        "Solution for part 1 is {}",
-        solve_part1(contraption.clone())
-    );
-    // contraption.reset();
-    println!("Solution for part 2 is {}", solve_part2(contraption));
+    println!("Solution for part 1 is {}", solve_part1(&contraption));
+    println!("Solution for part 2 is {}", solve_part2(&contraption));
 }
 
+#[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 mod test {
 
-    use super::{solve_part1, solve_part2, Contraption};
+    use super::{solve_part1, solve_part2, Contraption, Direction, Trajectory};
 
     const EXAMPLE: [&str; 10] = [
         ".|...\\....",
@@ -89,13 +44,46 @@ mod test {
     #[test]
     fn solve_example_part1() {
         let contraption: Contraption = Contraption::from(EXAMPLE.map(String::from).to_vec());
-        assert_eq!(solve_part1(contraption), 46);
+        assert_eq!(solve_part1(&contraption), 46);
     }
 
     #[test]
     fn solve_example_part2() {
         let contraption: Contraption = Contraption::from(EXAMPLE.map(String::from).to_vec());
-        assert_eq!(solve_part2(contraption), 51);
+        assert_eq!(solve_part2(&contraption), 51);
+    }
+
+    #[test]
+    fn example_best_entry() {
+        let contraption: Contraption = Contraption::from(EXAMPLE.map(String::from).to_vec());
+        assert_eq!(
+            contraption.best_entry(),
+            Some((Trajectory::new(0, 3, Direction::South), 51))
+        );
+    }
+
+    #[test]
+    fn walk_does_not_mutate() {
+        let contraption: Contraption = Contraption::from(EXAMPLE.map(String::from).to_vec());
+        let start = Trajectory::new(0, 0, Direction::East);
+
+        let first = contraption.walk(start);
+        let second = contraption.walk(start);
+
+        assert_eq!(first, second);
+        assert!(first.contains(0, 0));
+        assert!(!first.contains(0, 9));
+        // Out of range columns don't wrap into the next row
+        assert!(first.contains(1, 1));
+        assert!(!first.contains(0, 11));
+    }
+
+    #[test]
+    fn empty_contraption() {
+        let contraption: Contraption = Contraption::from(Vec::new());
+
+        assert_eq!(contraption.best_entry(), None);
+        assert_eq!(solve_part2(&contraption), 0);
     }
 }
 
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 #[derive(Debug, Clone)]
 pub struct DigPlan {
     instructions: Vec<Instruction>,
@@ -8,6 +10,90 @@ impl DigPlan {
         &self.instructions
     }
 
+    /// Start and end position of each trench segment, beginning at the origin.
+    pub fn segments(&self) -> Vec<(Position, Position)> {
+        let mut pos = Position::default();
+        self.instructions
+            .iter()
+            .map(|instr| {
+                let next_pos = pos.advance(instr);
+                let segment = (pos, next_pos);
+                pos = next_pos;
+                segment
+            })
+            .collect()
+    }
+
+    /// Check the trench returns to where it started without crossing or touching itself.
+    pub fn validate(&self) -> Result<(), DigPlanError> {
+        let segments = self.segments();
+
+        let end = segments.last().map_or(Position::default(), |(_, end)| *end);
+        if end != Position::default() {
+            return Err(DigPlanError::NotClosed { end });
+        }
+
+        let last = segments.len().saturating_sub(1);
+        for (i, lhs) in segments.iter().enumerate() {
+            for (j, rhs) in segments.iter().enumerate().skip(i + 1) {
+                let adjacent = j == i + 1 || (i == 0 && j == last);
+                let intersects = match adjacent {
+                    // Neighbours always share an endpoint, so only doubling back overlaps
+                    true => self.instructions[i]
+                        .direction
+                        .is_opposite(&self.instructions[j].direction),
+                    false => overlaps(lhs, rhs),
+                };
+
+                if intersects {
+                    return Err(DigPlanError::SelfIntersecting {
+                        first: i,
+                        second: j,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Render the trench as an SVG, filling the lagoon and stroking each edge in its color.
+    pub fn to_svg(&self) -> String {
+        let segments = self.segments();
+
+        let min_x = segments.iter().map(|(start, _)| start.x).min().unwrap_or(0);
+        let max_x = segments.iter().map(|(start, _)| start.x).max().unwrap_or(0);
+        let min_y = segments.iter().map(|(start, _)| start.y).min().unwrap_or(0);
+        let max_y = segments.iter().map(|(start, _)| start.y).max().unwrap_or(0);
+
+        let points: Vec<String> = segments
+            .iter()
+            .map(|(start, _)| format!("{},{}", start.x, start.y))
+            .collect();
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            min_x - 1,
+            min_y - 1,
+            max_x - min_x + 2,
+            max_y - min_y + 2
+        );
+        svg.push_str(&format!(
+            "  <polygon points=\"{}\" fill=\"#d0d0d0\" />\n",
+            points.join(" ")
+        ));
+
+        for ((start, end), instr) in segments.iter().zip(self.instructions.iter()) {
+            svg.push_str(&format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"2\" vector-effect=\"non-scaling-stroke\" />\n",
+                start.x, start.y, end.x, end.y, instr.color
+            ));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
     pub fn area(&self) -> i64 {
         let (area, perimeter, _pos) = self.instructions().iter().fold(
             (0, 0, Position::default()),
@@ -38,16 +124,16 @@ impl DigPlan {
                     _ => panic!("Invalid direction"),
                 };
                 let distance = parts.next().unwrap().parse().unwrap();
+                let color = parse_color(parts.next().unwrap());
 
                 Instruction {
                     direction,
                     distance,
+                    color,
                 }
             })
             .collect();
-        Self {
-            instructions: instructions,
-        }
+        Self { instructions }
     }
 
     pub fn from_part2(lines: Vec<String>) -> Self {
@@ -58,13 +144,8 @@ impl DigPlan {
                 parts.next();
                 parts.next();
 
-                let code = parts
-                    .next()
-                    .unwrap()
-                    .strip_suffix(")")
-                    .unwrap()
-                    .strip_prefix("(#")
-                    .unwrap();
+                let color = parse_color(parts.next().unwrap());
+                let code = color.strip_prefix('#').unwrap();
 
                 let (distance_hex, dir_code) = code.split_at(5);
 
@@ -81,11 +162,54 @@ impl DigPlan {
                 Instruction {
                     direction,
                     distance,
+                    color,
                 }
             })
             .collect();
-        Self {
-            instructions: instructions,
+        Self { instructions }
+    }
+}
+
+// "(#70c710)" -> "#70c710"
+fn parse_color(value: &str) -> String {
+    value
+        .strip_prefix('(')
+        .and_then(|value| value.strip_suffix(')'))
+        .unwrap()
+        .to_string()
+}
+
+/// Whether two axis-aligned segments share any point.
+fn overlaps(lhs: &(Position, Position), rhs: &(Position, Position)) -> bool {
+    let (lhs_min_x, lhs_max_x) = (lhs.0.x.min(lhs.1.x), lhs.0.x.max(lhs.1.x));
+    let (lhs_min_y, lhs_max_y) = (lhs.0.y.min(lhs.1.y), lhs.0.y.max(lhs.1.y));
+    let (rhs_min_x, rhs_max_x) = (rhs.0.x.min(rhs.1.x), rhs.0.x.max(rhs.1.x));
+    let (rhs_min_y, rhs_max_y) = (rhs.0.y.min(rhs.1.y), rhs.0.y.max(rhs.1.y));
+
+    lhs_min_x.max(rhs_min_x) <= lhs_max_x.min(rhs_max_x)
+        && lhs_min_y.max(rhs_min_y) <= lhs_max_y.min(rhs_max_y)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DigPlanError {
+    NotClosed { end: Position },
+    SelfIntersecting { first: usize, second: usize },
+}
+
+impl Display for DigPlanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DigPlanError::NotClosed { end } => write!(
+                f,
+                "trench ends at ({}, {}) instead of returning to the start",
+                end.x, end.y
+            ),
+            DigPlanError::SelfIntersecting { first, second } => write!(
+                f,
+                "trench crosses itself at instructions {} and {}",
+                first + 1,
+                second + 1
+            ),
         }
     }
 }
@@ -94,6 +218,7 @@ impl DigPlan {
 pub struct Instruction {
     direction: Direction,
     distance: i64,
+    color: String,
 }
 
 impl Instruction {
@@ -104,9 +229,13 @@ impl Instruction {
     pub fn distance(&self) -> i64 {
         self.distance
     }
+
+    pub fn color(&self) -> &str {
+        &self.color
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Direction {
     U,
     D,
@@ -114,17 +243,24 @@ pub enum Direction {
     R,
 }
 
+impl Direction {
+    pub fn is_opposite(&self, other: &Direction) -> bool {
+        matches!(
+            (self, other),
+            (Direction::U, Direction::D)
+                | (Direction::D, Direction::U)
+                | (Direction::L, Direction::R)
+                | (Direction::R, Direction::L)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
 pub struct Position {
     x: i64,
     y: i64,
 }
 
-impl Default for Position {
-    fn default() -> Self {
-        Self { x: 0, y: 0 }
-    }
-}
-
 impl Position {
     pub fn new(x: i64, y: i64) -> Self {
         Self { x, y }
@@ -4,21 +4,46 @@ use std::fs::read_to_string;
 
 use dig_plan::DigPlan;
 
+fn validated(plan: DigPlan) -> DigPlan {
+    match plan.validate() {
+        Ok(()) => plan,
+        Err(err) => panic!("Invalid dig plan: {err}"),
+    }
+}
+
+/// Write the part 1 plan, the one with colored edges, to `path` as an SVG.
+fn export_svg(plan: &DigPlan, path: &str) {
+    match std::fs::write(path, plan.to_svg()) {
+        Ok(()) => println!("Wrote trench to {path}"),
+        Err(err) => eprintln!("Could not write trench to {path}: {err}"),
+    }
+}
+
 fn main() {
-    println!(
-        "Solution for part 1 is {}",
-        DigPlan::from_part1(read_lines("input.txt")).area()
-    );
+    let plan = validated(DigPlan::from_part1(read_lines("input.txt")));
+
+    // Usage: day-18 [--svg <path>]
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|arg| arg == "--svg") {
+        match args.get(pos + 1) {
+            Some(path) => export_svg(&plan, path),
+            None => eprintln!("Usage: {} [--svg <path>]", args[0]),
+        }
+    }
+
+    println!("Solution for part 1 is {}", plan.area());
 
     println!(
         "Solution for part 2 is {}",
-        DigPlan::from_part2(read_lines("input.txt")).area()
+        validated(DigPlan::from_part2(read_lines("input.txt"))).area()
     );
 }
 
+#[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 mod test {
 
+    use super::dig_plan::DigPlanError;
     use super::DigPlan;
 
     const EXAMPLE: [&str; 14] = [
@@ -49,6 +74,67 @@ mod test {
         let plan: DigPlan = DigPlan::from_part2(EXAMPLE.map(String::from).to_vec());
         assert_eq!(plan.area(), 952408144115);
     }
+
+    #[test]
+    fn validate_example() {
+        let plan: DigPlan = DigPlan::from_part1(EXAMPLE.map(String::from).to_vec());
+        assert_eq!(plan.validate(), Ok(()));
+
+        let plan: DigPlan = DigPlan::from_part2(EXAMPLE.map(String::from).to_vec());
+        assert_eq!(plan.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_not_closed() {
+        let plan: DigPlan =
+            DigPlan::from_part1(EXAMPLE[..13].iter().map(|s| s.to_string()).collect());
+        assert!(matches!(
+            plan.validate(),
+            Err(DigPlanError::NotClosed { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_self_intersecting() {
+        // A figure eight, crossing at (1, 1)
+        let lines = [
+            "R 2 (#000000)",
+            "D 2 (#000000)",
+            "L 1 (#000000)",
+            "U 3 (#000000)",
+            "L 1 (#000000)",
+            "D 1 (#000000)",
+        ];
+        let plan: DigPlan = DigPlan::from_part1(lines.map(String::from).to_vec());
+        assert_eq!(
+            plan.validate(),
+            Err(DigPlanError::SelfIntersecting {
+                first: 0,
+                second: 3
+            })
+        );
+
+        let lines = ["R 2 (#000000)", "L 1 (#000000)", "L 1 (#000000)"];
+        let plan: DigPlan = DigPlan::from_part1(lines.map(String::from).to_vec());
+        assert_eq!(
+            plan.validate(),
+            Err(DigPlanError::SelfIntersecting {
+                first: 0,
+                second: 1
+            })
+        );
+    }
+
+    #[test]
+    fn svg_keeps_colors() {
+        let plan: DigPlan = DigPlan::from_part1(EXAMPLE.map(String::from).to_vec());
+        let svg = plan.to_svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("<polygon points=\"0,0 6,0 6,5"));
+        assert!(svg.contains("x1=\"0\" y1=\"0\" x2=\"6\" y2=\"0\" stroke=\"#70c710\""));
+        assert_eq!(svg.matches("<line").count(), EXAMPLE.len());
+    }
 }
 
 // Utilities
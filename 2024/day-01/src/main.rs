@@ -1,19 +1,5 @@
-use std::{collections::HashMap, fs::read_to_string};
-
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-pub struct Row {
-    first: i64,
-    second: i64,
-}
-
-impl From<&String> for Row {
-    fn from(s: &String) -> Self {
-        let mut parts = s.split_whitespace();
-        let first = parts.next().unwrap().parse::<i64>().unwrap();
-        let second = parts.next().unwrap().parse::<i64>().unwrap();
-        Self { first, second }
-    }
-}
+use rust_aoc_utils::parse_n_columns;
+use std::fs::read_to_string;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Lists {
@@ -22,96 +8,68 @@ pub struct Lists {
 }
 
 impl Lists {
-    pub fn first(&self) -> &Vec<i64> {
-        &self.first
-    }
-
-    pub fn second(&self) -> &Vec<i64> {
-        &self.second
-    }
-}
-
-impl From<Vec<Row>> for Lists {
-    fn from(rows: Vec<Row>) -> Self {
-        let mut first = vec![];
-        let mut second = vec![];
-        for row in rows {
-            first.push(row.first);
-            second.push(row.second);
-        }
-
+    /// Both lists are kept sorted, so pairing them up is a single pass.
+    pub fn new(mut first: Vec<i64>, mut second: Vec<i64>) -> Self {
         first.sort();
         second.sort();
 
         Self { first, second }
     }
-}
 
-impl Iterator for Lists {
-    type Item = (i64, i64);
+    /// Sum of the distances between the smallest values, the second smallest, and so on.
+    pub fn distance(&self) -> u64 {
+        self.first
+            .iter()
+            .zip(self.second.iter())
+            .map(|(first, second)| (second - first).unsigned_abs())
+            .sum()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.first.is_empty() || self.second.is_empty() {
-            return None;
+    /// Sum of each value in the first list times how often it appears in the second,
+    /// counted by walking both sorted lists together.
+    pub fn similarity(&self) -> i64 {
+        let mut result = 0;
+        let mut pos = 0;
+
+        for &number in &self.first {
+            while pos < self.second.len() && self.second[pos] < number {
+                pos += 1;
+            }
+
+            let count = self.second[pos..]
+                .iter()
+                .take_while(|other| **other == number)
+                .count();
+            result += number * count as i64;
         }
 
-        let first = self.first.remove(0);
-        let second = self.second.remove(0);
-
-        Some((first, second))
+        result
     }
-}
-
-fn part1(lines: Vec<String>) -> usize {
-    let lists: Lists = Lists::from(lines.iter().map(Row::from).collect::<Vec<_>>());
 
-    lists.into_iter().fold(0usize, |acc, (first, second)| {
-        let diff: i64 = second - first;
-        acc + diff.unsigned_abs() as usize
-    })
-}
-
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-struct Counter {
-    amount: usize,
-}
-
-impl Counter {
-    fn new() -> Self {
-        Self { amount: 0 }
+    pub fn first(&self) -> &[i64] {
+        &self.first
     }
 
-    fn increment(&mut self) {
-        self.amount += 1;
+    pub fn second(&self) -> &[i64] {
+        &self.second
     }
+}
 
-    fn amount(&self) -> usize {
-        self.amount
+impl From<&[String]> for Lists {
+    fn from(lines: &[String]) -> Self {
+        match parse_n_columns::<i64, 2>(lines) {
+            Ok([first, second]) => Lists::new(first, second),
+            Err(err) => panic!("Invalid location lists: {err}"),
+        }
     }
 }
 
-impl Default for Counter {
-    fn default() -> Self {
-        Self::new()
-    }
+fn part1(lines: Vec<String>) -> usize {
+    Lists::from(&lines[..]).distance() as usize
 }
 
 fn part2(lines: Vec<String>) -> usize {
-    let lists: Lists = Lists::from(lines.iter().map(Row::from).collect::<Vec<_>>());
-
-    let mut counts: HashMap<i64, Counter> = HashMap::new();
-
-    for &number in lists.second() {
-        counts.entry(number).or_default().increment();
-    }
-
-    let mut result: usize = 0;
-    for &number in lists.first() {
-        if let Some(counter) = counts.get(&number) {
-            result += number as usize * counter.amount();
-        }
-    }
-    result
+    Lists::from(&lines[..]).similarity() as usize
 }
 
 fn main() {
@@ -131,8 +89,7 @@ fn read_lines(filename: &str) -> Vec<String> {
 
 #[cfg(test)]
 mod test {
-    use super::part1;
-    use super::part2;
+    use super::{part1, part2, Lists};
 
     const EXAMPLE: [&str; 6] = ["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"];
 
@@ -145,4 +102,13 @@ mod test {
     fn solve_example_part2() {
         assert_eq!(part2(EXAMPLE.map(String::from).to_vec()), 31);
     }
+
+    #[test]
+    fn similarity_with_repeats() {
+        let lists = Lists::new(vec![3, 3, 1, 5], vec![3, 5, 3, 3, 2]);
+
+        assert_eq!(lists.first(), &[1, 3, 3, 5]);
+        assert_eq!(lists.similarity(), 3 * 3 + 3 * 3 + 5);
+        assert_eq!(lists.distance(), 3);
+    }
 }
@@ -1,6 +1,6 @@
 use std::fs::read_to_string;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Direction {
     Ascending,
     Descending,
@@ -13,29 +13,114 @@ fn get_direction(lhs: i64, rhs: i64) -> Direction {
     }
 }
 
-fn is_safe(levels: &[i64]) -> bool {
-    let initial_direction: Direction = get_direction(levels[0], levels[1]);
+impl Direction {
+    fn allows(&self, lhs: i64, rhs: i64) -> bool {
+        get_direction(lhs, rhs) == *self && (1..=3).contains(&(rhs - lhs).unsigned_abs())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Rule {
+    /// The levels stopped increasing or decreasing in the same direction as they started.
+    DirectionChange,
+    /// Adjacent levels differ by this much, outside of 1 to 3.
+    StepSize(u64),
+}
+
+/// First rule broken in a report, between the levels at `index - 1` and `index`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Violation {
+    pub rule: Rule,
+    pub index: usize,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SafetyVerdict {
+    Safe,
+    /// Unsafe as recorded, but safe once the levels at `removed` are dropped.
+    Dampened {
+        violation: Violation,
+        removed: Vec<usize>,
+    },
+    Unsafe {
+        violation: Violation,
+    },
+}
+
+impl SafetyVerdict {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, SafetyVerdict::Unsafe { .. })
+    }
+}
+
+fn find_violation(levels: &[i64]) -> Option<Violation> {
+    let initial_direction = get_direction(levels[0], levels[1]);
+
+    levels
+        .windows(2)
+        .enumerate()
+        .find_map(|(i, pair)| match (pair[1] - pair[0]).unsigned_abs() {
+            1..=3 if get_direction(pair[0], pair[1]) == initial_direction => None,
+            1..=3 => Some(Violation {
+                rule: Rule::DirectionChange,
+                index: i + 1,
+            }),
+            d => Some(Violation {
+                rule: Rule::StepSize(d),
+                index: i + 1,
+            }),
+        })
+}
+
+/// Fewest levels to remove, up to `max_removals`, so the rest all move in `direction`.
+///
+/// Tracks the last kept level and the removals so far, so each level only looks back at
+/// the `max_removals + 1` levels before it, which is O(n) for a fixed number of removals.
+fn find_removals(levels: &[i64], direction: Direction, max_removals: usize) -> Option<Vec<usize>> {
+    let len = levels.len();
+    if len == 0 {
+        return Some(Vec::new());
+    }
 
-    let mut current = levels[0];
-    for (i, &next) in levels.iter().enumerate().skip(1) {
-        let diff = (next - current).unsigned_abs();
-        let dir = get_direction(current, next);
-        match diff {
-            1..=3 => match dir == initial_direction {
-                false => {
-                    println!("direction mismatch at index {}", i);
-                    return false;
+    // reached[i][j] is set when level i can be kept after j removals, holding the
+    // previously kept level, or None when every level before i was removed
+    let mut reached: Vec<Vec<Option<Option<usize>>>> = vec![vec![None; max_removals + 1]; len];
+    for i in 0..len {
+        if i <= max_removals {
+            reached[i][i] = Some(None);
+        }
+
+        for skipped in 0..i.min(max_removals + 1) {
+            let prev = i - 1 - skipped;
+            if !direction.allows(levels[prev], levels[i]) {
+                continue;
+            }
+
+            for removed in skipped..=max_removals {
+                if reached[i][removed].is_none() && reached[prev][removed - skipped].is_some() {
+                    reached[i][removed] = Some(Some(prev));
                 }
-                true => {}
-            },
-            d => {
-                println!("{} difference at index {}", d, i);
-                return false;
             }
         }
-        current = next
     }
-    true
+
+    let (last, removed) = (0..len)
+        .flat_map(|i| (0..=max_removals).map(move |removed| (i, removed)))
+        .filter(|(i, removed)| reached[*i][*removed].is_some())
+        .filter(|(i, removed)| removed + (len - 1 - i) <= max_removals)
+        .min_by_key(|(i, removed)| removed + (len - 1 - i))?;
+
+    let mut removals: Vec<usize> = (last + 1..len).collect();
+    let (mut current, mut removed) = (last, removed);
+    while let Some(Some(prev)) = reached[current][removed] {
+        removals.extend(prev + 1..current);
+        removed -= current - prev - 1;
+        current = prev;
+    }
+    removals.extend(0..current);
+    removals.sort();
+
+    Some(removals)
 }
 
 #[derive(Debug)]
@@ -44,24 +129,31 @@ struct Report {
 }
 
 impl Report {
+    /// Whether the report is safe, allowing up to `max_removals` levels to be dropped.
+    fn verdict(&self, max_removals: usize) -> SafetyVerdict {
+        if self.levels.len() < 2 {
+            return SafetyVerdict::Safe;
+        }
+
+        let Some(violation) = find_violation(&self.levels) else {
+            return SafetyVerdict::Safe;
+        };
+
+        [Direction::Ascending, Direction::Descending]
+            .into_iter()
+            .filter_map(|direction| find_removals(&self.levels, direction, max_removals))
+            .min_by_key(Vec::len)
+            .map_or(SafetyVerdict::Unsafe { violation }, |removed| {
+                SafetyVerdict::Dampened { violation, removed }
+            })
+    }
+
     fn safe(&self) -> bool {
-        is_safe(&self.levels)
+        self.verdict(0).is_safe()
     }
 
     fn safe_with_dampener(&self) -> bool {
-        if is_safe(&self.levels) {
-            return true;
-        }
-
-        for i in 0..self.levels.len() {
-            let mut temp = self.levels.clone();
-            temp.remove(i);
-            if is_safe(&temp) {
-                return true;
-            }
-        }
-
-        false
+        self.verdict(1).is_safe()
     }
 }
 
@@ -77,39 +169,15 @@ impl From<&String> for Report {
 }
 
 fn part1(lines: Vec<String>) -> usize {
-    let reports = lines.iter().map(Report::from).collect::<Vec<_>>();
-
-    println!("Num reports: {:?}", reports.len());
-
-    let mut result = 0;
-    for report in reports {
-        let is_safe = report.safe();
-        if is_safe {
-            result += 1;
-        }
-
-        println!("\t{:?}: {:?}", report.levels, is_safe);
-    }
-
-    result
+    lines.iter().map(Report::from).filter(Report::safe).count()
 }
 
 fn part2(lines: Vec<String>) -> usize {
-    let reports = lines.iter().map(Report::from).collect::<Vec<_>>();
-
-    println!("Num reports: {:?}", reports.len());
-
-    let mut result = 0;
-    for report in reports {
-        let is_safe = report.safe() || report.safe_with_dampener();
-        if is_safe {
-            result += 1;
-        }
-
-        println!("\t{:?}: {:?}", report.levels, is_safe);
-    }
-
-    result
+    lines
+        .iter()
+        .map(Report::from)
+        .filter(Report::safe_with_dampener)
+        .count()
 }
 
 fn main() {
@@ -127,8 +195,7 @@ fn read_lines(filename: &str) -> Vec<String> {
 
 #[cfg(test)]
 mod test {
-    use super::part1;
-    use super::part2;
+    use super::{part1, part2, Report, Rule, SafetyVerdict, Violation};
 
     const EXAMPLE: [&str; 6] = [
         "7 6 4 2 1",
@@ -148,4 +215,62 @@ mod test {
     fn solve_example_part2() {
         assert_eq!(part2(EXAMPLE.map(String::from).to_vec()), 4);
     }
+
+    fn report(line: &str) -> Report {
+        Report::from(&line.to_string())
+    }
+
+    #[test]
+    fn verdicts_with_one_removal() {
+        assert_eq!(report("7 6 4 2 1").verdict(1), SafetyVerdict::Safe);
+        assert_eq!(
+            report("1 2 7 8 9").verdict(1),
+            SafetyVerdict::Unsafe {
+                violation: Violation {
+                    rule: Rule::StepSize(5),
+                    index: 2
+                }
+            }
+        );
+        assert_eq!(
+            report("1 3 2 4 5").verdict(1),
+            SafetyVerdict::Dampened {
+                violation: Violation {
+                    rule: Rule::DirectionChange,
+                    index: 2
+                },
+                removed: vec![1]
+            }
+        );
+        assert_eq!(
+            report("8 6 4 4 1").verdict(1),
+            SafetyVerdict::Dampened {
+                violation: Violation {
+                    rule: Rule::StepSize(0),
+                    index: 3
+                },
+                removed: vec![2]
+            }
+        );
+    }
+
+    #[test]
+    fn removals_at_the_edges() {
+        let verdict = report("9 1 2 3 4").verdict(1);
+        assert!(matches!(verdict, SafetyVerdict::Dampened { removed, .. } if removed == vec![0]));
+
+        let verdict = report("1 2 3 4 9").verdict(1);
+        assert!(matches!(verdict, SafetyVerdict::Dampened { removed, .. } if removed == vec![4]));
+    }
+
+    #[test]
+    fn multiple_removals() {
+        let report = report("1 9 2 9 3 4");
+
+        assert!(!report.verdict(1).is_safe());
+        assert!(matches!(
+            report.verdict(2),
+            SafetyVerdict::Dampened { removed, .. } if removed == vec![1, 3]
+        ));
+    }
 }
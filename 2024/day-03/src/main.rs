@@ -1,51 +1,219 @@
-use once_cell::sync::Lazy;
-use regex::Regex;
-use std::fs::read_to_string;
+use std::{fmt::Display, fs::read_to_string, ops::RangeInclusive};
 
-static MULTS_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"(mul\((?<lhs>[\d]+),(?<rhs>[\d]+)\))").unwrap());
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Instruction {
+    Mul(u64, u64),
+    Do,
+    Dont,
+}
 
-fn find_mults(input: &str, do_mults: bool) -> Vec<u64> {
-    if !do_mults {
-        return vec![];
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Mul(lhs, rhs) => write!(f, "mul({lhs},{rhs})"),
+            Instruction::Do => write!(f, "do()"),
+            Instruction::Dont => write!(f, "don't()"),
+        }
     }
+}
+
+/// How an instruction is spelled in memory: `name(arg,arg,...)`, where every
+/// argument is a number with a digit count in `digits`.
+#[derive(Debug, Clone)]
+pub struct Opcode {
+    pub name: &'static str,
+    pub arity: usize,
+    pub digits: RangeInclusive<usize>,
+    pub build: fn(&[u64]) -> Instruction,
+}
+
+pub const MUL: Opcode = Opcode {
+    name: "mul",
+    arity: 2,
+    digits: 1..=3,
+    build: |args| Instruction::Mul(args[0], args[1]),
+};
+
+pub const DO: Opcode = Opcode {
+    name: "do",
+    arity: 0,
+    digits: 0..=0,
+    build: |_| Instruction::Do,
+};
+
+pub const DONT: Opcode = Opcode {
+    name: "don't",
+    arity: 0,
+    digits: 0..=0,
+    build: |_| Instruction::Dont,
+};
+
+pub const OPCODES: [Opcode; 3] = [MUL, DO, DONT];
 
-    MULTS_RE
-        .captures_iter(input)
-        .map(|capture| {
-            let lhs = capture.get(2).unwrap().as_str().parse::<u64>().unwrap();
-            let rhs = capture.get(3).unwrap().as_str().parse::<u64>().unwrap();
+impl Opcode {
+    /// Length of the instruction at the start of `input` along with its arguments,
+    /// if it matches exactly.
+    fn matches(&self, input: &str) -> Option<(usize, Vec<u64>)> {
+        let mut rest = input.strip_prefix(self.name)?.strip_prefix('(')?;
+        let mut args: Vec<u64> = Vec::with_capacity(self.arity);
 
-            lhs * rhs
-        })
-        .collect::<Vec<u64>>()
+        for arg in 0..self.arity {
+            if arg > 0 {
+                rest = rest.strip_prefix(',')?;
+            }
+
+            let num_digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+            if !self.digits.contains(&num_digits) {
+                return None;
+            }
+
+            args.push(rest[..num_digits].parse().ok()?);
+            rest = &rest[num_digits..];
+        }
+
+        rest = rest.strip_prefix(')')?;
+
+        Some((input.len() - rest.len(), args))
+    }
 }
 
-fn part1(input: String) -> u64 {
-    find_mults(&input, true).iter().sum()
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Token {
+    pub offset: usize,
+    pub len: usize,
+    pub instruction: Instruction,
 }
 
-fn part2(input: String) -> u64 {
-    let mut result = 0;
-    let mut do_mults = true;
-
-    let buffer: &mut String = &mut String::new();
-    for ch in input.chars() {
-        buffer.push(ch);
-        if buffer.ends_with("do()") {
-            result += find_mults(buffer, do_mults).iter().sum::<u64>();
-            do_mults = true;
-            buffer.clear();
-        } else if buffer.ends_with("don't()") {
-            result += find_mults(buffer, do_mults).iter().sum::<u64>();
-            do_mults = false;
-            buffer.clear();
+/// Single pass over corrupted memory, yielding each valid instruction in order.
+pub struct Tokenizer<'a> {
+    input: &'a str,
+    pos: usize,
+    opcodes: &'a [Opcode],
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(input: &'a str, opcodes: &'a [Opcode]) -> Self {
+        Tokenizer {
+            input,
+            pos: 0,
+            opcodes,
+        }
+    }
+}
+
+impl Iterator for Tokenizer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.input.len() {
+            let offset = self.pos;
+            let rest = &self.input.as_bytes()[offset..];
+
+            // Every opcode name is ASCII, so slicing only at matching bytes stays on char boundaries
+            let found = self
+                .opcodes
+                .iter()
+                .filter(|opcode| rest.starts_with(opcode.name.as_bytes()))
+                .find_map(|opcode| {
+                    opcode
+                        .matches(&self.input[offset..])
+                        .map(|(len, args)| (len, (opcode.build)(&args)))
+                });
+
+            match found {
+                Some((len, instruction)) => {
+                    self.pos += len;
+                    return Some(Token {
+                        offset,
+                        len,
+                        instruction,
+                    });
+                }
+                None => self.pos += 1,
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Step {
+    pub token: Token,
+    pub executed: bool,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self.executed {
+            true => "executed",
+            false => "skipped",
+        };
+
+        write!(
+            f,
+            "{state} {} at byte {}",
+            self.token.instruction, self.token.offset
+        )
+    }
+}
+
+/// Runs instructions, where `don't()` disables multiplications until the next `do()`
+/// unless conditionals are ignored.
+#[derive(Debug, Clone)]
+pub struct Interpreter {
+    pub conditionals: bool,
+    pub enabled: bool,
+    pub total: u64,
+    pub trace: Vec<Step>,
+}
+
+impl Interpreter {
+    pub fn new(conditionals: bool) -> Self {
+        Interpreter {
+            conditionals,
+            enabled: true,
+            total: 0,
+            trace: Vec::new(),
         }
     }
 
-    result += find_mults(buffer, do_mults).iter().sum::<u64>();
+    pub fn execute(&mut self, token: Token) {
+        let executed = match token.instruction {
+            Instruction::Mul(lhs, rhs) => {
+                if self.enabled {
+                    self.total += lhs * rhs;
+                }
+                self.enabled
+            }
+            Instruction::Do => {
+                self.enabled = true;
+                self.conditionals
+            }
+            Instruction::Dont => {
+                self.enabled = !self.conditionals;
+                self.conditionals
+            }
+        };
+
+        self.trace.push(Step { token, executed });
+    }
 
-    result
+    pub fn run(mut self, input: &str) -> Self {
+        for token in Tokenizer::new(input, &OPCODES) {
+            self.execute(token);
+        }
+
+        self
+    }
+}
+
+fn part1(input: String) -> u64 {
+    Interpreter::new(false).run(&input).total
+}
+
+fn part2(input: String) -> u64 {
+    Interpreter::new(true).run(&input).total
 }
 
 fn main() {
@@ -64,8 +232,7 @@ fn read_input(filename: &str) -> String {
 
 #[cfg(test)]
 mod test {
-    use super::part1;
-    use super::part2;
+    use super::{part1, part2, Instruction, Interpreter, Opcode, Token, Tokenizer, MUL, OPCODES};
 
     const EXAMPLE_PART_1: &str =
         "mul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
@@ -82,4 +249,72 @@ mod test {
     fn solve_example_part2() {
         assert_eq!(part2(EXAMPLE_PART_2.to_string()), 48);
     }
+
+    #[test]
+    fn tokenize_with_offsets() {
+        let tokens: Vec<Token> = Tokenizer::new(EXAMPLE_PART_2, &OPCODES).collect();
+
+        assert_eq!(
+            tokens[..2],
+            [
+                Token {
+                    offset: 1,
+                    len: 8,
+                    instruction: Instruction::Mul(2, 4)
+                },
+                Token {
+                    offset: 20,
+                    len: 7,
+                    instruction: Instruction::Dont
+                },
+            ]
+        );
+        assert_eq!(tokens.len(), 6);
+    }
+
+    #[test]
+    fn argument_digit_limits() {
+        let tokens: Vec<Instruction> = Tokenizer::new("mul(1234,5)mul(,5)mul(123,4)", &[MUL])
+            .map(|token| token.instruction)
+            .collect();
+
+        assert_eq!(tokens, vec![Instruction::Mul(123, 4)]);
+    }
+
+    #[test]
+    fn custom_opcodes() {
+        let opcodes = [Opcode {
+            name: "sq",
+            arity: 1,
+            digits: 1..=2,
+            build: |args| Instruction::Mul(args[0], args[0]),
+        }];
+        let tokens: Vec<Instruction> = Tokenizer::new("sq(12)sq(123)mul(2,2)", &opcodes)
+            .map(|token| token.instruction)
+            .collect();
+
+        assert_eq!(tokens, vec![Instruction::Mul(12, 12)]);
+    }
+
+    #[test]
+    fn trace_executed_and_skipped() {
+        let interpreter = Interpreter::new(true).run(EXAMPLE_PART_2);
+        let trace: Vec<String> = interpreter
+            .trace
+            .iter()
+            .map(|step| step.to_string())
+            .collect();
+
+        assert_eq!(
+            trace,
+            vec![
+                "executed mul(2,4) at byte 1",
+                "executed don't() at byte 20",
+                "skipped mul(5,5) at byte 28",
+                "skipped mul(11,8) at byte 48",
+                "executed do() at byte 59",
+                "executed mul(8,5) at byte 64",
+            ]
+        );
+    }
 }
@@ -1,8 +1,14 @@
-use std::{collections::HashMap, fs::read_to_string};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+    fs::read_to_string,
+};
 
+/// Page ordering rules as a graph, with an edge from each page to every page that must follow it.
 #[derive(Debug, Default)]
 pub struct OrderingRules {
-    ordering_rules: HashMap<u64, Vec<u64>>,
+    ordering_rules: HashMap<u64, HashSet<u64>>,
 }
 
 impl OrderingRules {
@@ -10,22 +16,189 @@ impl OrderingRules {
         self.ordering_rules
             .entry(before_page)
             .or_default()
-            .push(after_page);
+            .insert(after_page);
+    }
+
+    pub fn must_precede(&self, before_page: u64, after_page: u64) -> bool {
+        self.ordering_rules
+            .get(&before_page)
+            .is_some_and(|after_pages| after_pages.contains(&after_page))
     }
 
     pub fn is_in_valid_order(&self, before_page: u64, after_pages: &[u64]) -> bool {
+        !after_pages
+            .iter()
+            .any(|after_page| self.must_precede(*after_page, before_page))
+    }
+
+    /// Comparator for `sort_by`, ordering pages by the rule between them.
+    ///
+    /// This is only a total order when every pair of pages being sorted has a rule
+    /// between them and those rules are acyclic, so a sorted update still needs checking.
+    pub fn compare(&self, lhs: u64, rhs: u64) -> Ordering {
+        if self.must_precede(lhs, rhs) {
+            Ordering::Less
+        } else if self.must_precede(rhs, lhs) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Pages in `pages` that must follow `page`.
+    fn successors<'a>(
+        &'a self,
+        page: u64,
+        pages: &'a HashSet<u64>,
+    ) -> impl Iterator<Item = u64> + 'a {
+        self.ordering_rules
+            .get(&page)
+            .into_iter()
+            .flat_map(move |after_pages| after_pages.iter().filter(|after| pages.contains(after)))
+            .copied()
+    }
+
+    /// Order `pages` so every rule between them is followed, or report the cyclic rules
+    /// that make that impossible.
+    pub fn topological_order(&self, pages: &[u64]) -> Result<Vec<u64>, CyclicRules> {
+        let page_set: HashSet<u64> = pages.iter().copied().collect();
+        let mut in_degrees: HashMap<u64, usize> = pages.iter().map(|page| (*page, 0)).collect();
+        for before_page in pages {
+            for after_page in self.successors(*before_page, &page_set) {
+                *in_degrees.get_mut(&after_page).unwrap() += 1;
+            }
+        }
+
+        // Pages with no remaining predecessors, starting in their original order
+        let mut ready: VecDeque<u64> = pages
+            .iter()
+            .filter(|page| in_degrees[*page] == 0)
+            .copied()
+            .collect();
+        let mut ordered: Vec<u64> = Vec::with_capacity(pages.len());
+
+        while let Some(page) = ready.pop_front() {
+            ordered.push(page);
+            for after_page in self.successors(page, &page_set) {
+                let in_degree = in_degrees.get_mut(&after_page).unwrap();
+                *in_degree -= 1;
+                if *in_degree == 0 {
+                    ready.push_back(after_page);
+                }
+            }
+        }
+
+        match ordered.len() == pages.len() {
+            true => Ok(ordered),
+            false => Err(CyclicRules {
+                subsets: self.cyclic_subsets(pages),
+            }),
+        }
+    }
+
+    /// Groups of `pages` whose rules form a cycle, found as the strongly connected
+    /// components with more than one page.
+    pub fn cyclic_subsets(&self, pages: &[u64]) -> Vec<Vec<u64>> {
+        let page_set: HashSet<u64> = pages.iter().copied().collect();
+        let mut tarjan = Tarjan {
+            rules: self,
+            pages: &page_set,
+            index: HashMap::new(),
+            low_link: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            subsets: Vec::new(),
+        };
+
+        for page in pages {
+            if !tarjan.index.contains_key(page) {
+                tarjan.visit(*page);
+            }
+        }
+
+        let mut subsets = tarjan.subsets;
+        subsets.sort();
+        subsets
+    }
+
+    pub fn pages(&self) -> Vec<u64> {
+        let mut pages: Vec<u64> = self
+            .ordering_rules
+            .iter()
+            .flat_map(|(before_page, after_pages)| {
+                std::iter::once(*before_page).chain(after_pages.iter().copied())
+            })
+            .collect();
+        pages.sort();
+        pages.dedup();
+
+        pages
+    }
+}
+
+// Tarjan's strongly connected components, restricted to a set of pages
+struct Tarjan<'a> {
+    rules: &'a OrderingRules,
+    pages: &'a HashSet<u64>,
+    index: HashMap<u64, usize>,
+    low_link: HashMap<u64, usize>,
+    stack: Vec<u64>,
+    on_stack: HashSet<u64>,
+    subsets: Vec<Vec<u64>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, page: u64) {
+        let index = self.index.len();
+        self.index.insert(page, index);
+        self.low_link.insert(page, index);
+        self.stack.push(page);
+        self.on_stack.insert(page);
+
+        let after_pages: Vec<u64> = self.rules.successors(page, self.pages).collect();
         for after_page in after_pages {
-            if self
-                .ordering_rules
-                .get(after_page)
-                .unwrap_or(&vec![])
-                .contains(&before_page)
-            {
-                return false;
+            if !self.index.contains_key(&after_page) {
+                self.visit(after_page);
+                let low_link = self.low_link[&page].min(self.low_link[&after_page]);
+                self.low_link.insert(page, low_link);
+            } else if self.on_stack.contains(&after_page) {
+                let low_link = self.low_link[&page].min(self.index[&after_page]);
+                self.low_link.insert(page, low_link);
             }
         }
 
-        true
+        if self.low_link[&page] == self.index[&page] {
+            let mut subset: Vec<u64> = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(&member);
+                subset.push(member);
+                if member == page {
+                    break;
+                }
+            }
+
+            if subset.len() > 1 {
+                subset.sort();
+                self.subsets.push(subset);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CyclicRules {
+    pub subsets: Vec<Vec<u64>>,
+}
+
+impl Display for CyclicRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let subsets: Vec<String> = self
+            .subsets
+            .iter()
+            .map(|subset| format!("{subset:?}"))
+            .collect();
+
+        write!(f, "rules are cyclic between pages {}", subsets.join(", "))
     }
 }
 
@@ -45,12 +218,12 @@ impl From<Vec<String>> for InputData {
                 continue;
             }
             if row.contains('|') {
-                let mut parts = row.split("|");
+                let mut parts = row.split('|');
                 let first = parts.next().unwrap().parse::<u64>().unwrap();
                 let second = parts.next().unwrap().parse::<u64>().unwrap();
                 ordering_rules.add_rule(first, second);
             } else {
-                let parts = row.split(",");
+                let parts = row.split(',');
                 let update = parts
                     .map(|part| part.parse::<u64>().unwrap())
                     .collect::<Vec<_>>();
@@ -88,21 +261,27 @@ fn is_valid_update(update: &[u64], ordering_rules: &OrderingRules) -> bool {
     true
 }
 
-fn fix_update<'a>(update: &'a mut Vec<u64>, ordering_rules: &OrderingRules) -> &'a mut Vec<u64> {
-    for i in (0..update.len()).step_by(1) {
-        let page = update[i];
-        let next_page = safe_subslice(update, i + 1, i + 2);
-        if !ordering_rules.is_in_valid_order(page, next_page) {
-            // Swap the pages
-            update[i] = next_page[0];
-            update[i + 1] = page;
-        }
-    }
+/// Order the update by the rules, reporting any cycles between its pages.
+///
+/// `sort_by` may panic when its comparator is not a total order, so the update is only
+/// sorted with `compare` once the topological sort has shown the rules are acyclic and
+/// every pair of pages has a rule between them. Otherwise the topological order is used.
+fn fix_update(update: &[u64], ordering_rules: &OrderingRules) -> Result<Vec<u64>, CyclicRules> {
+    let ordered = ordering_rules.topological_order(update)?;
 
-    if is_valid_update(update, ordering_rules) {
-        update
-    } else {
-        fix_update(update, ordering_rules)
+    let is_total = ordered.iter().enumerate().all(|(i, before_page)| {
+        ordered[i + 1..]
+            .iter()
+            .all(|after_page| ordering_rules.must_precede(*before_page, *after_page))
+    });
+
+    match is_total {
+        true => {
+            let mut fixed = update.to_vec();
+            fixed.sort_by(|lhs, rhs| ordering_rules.compare(*lhs, *rhs));
+            Ok(fixed)
+        }
+        false => Ok(ordered),
     }
 }
 
@@ -123,10 +302,12 @@ fn solve(lines: Vec<String>) -> (u64, u64) {
         if is_valid_update(update, input_data.get_ordering_rules()) {
             part1_result += get_middle_element(update).unwrap();
         } else {
-            let mut update_clone = update.clone();
-            let fixed_update = fix_update(&mut update_clone, input_data.get_ordering_rules());
+            let fixed_update = match fix_update(update, input_data.get_ordering_rules()) {
+                Ok(fixed_update) => fixed_update,
+                Err(err) => panic!("Cannot fix update {update:?}: {err}"),
+            };
 
-            part2_result += get_middle_element(fixed_update).unwrap();
+            part2_result += get_middle_element(&fixed_update).unwrap();
         }
     }
 
@@ -149,17 +330,9 @@ fn read_lines(filename: &str) -> Vec<String> {
         .collect()
 }
 
-fn safe_subslice<T>(vec: &[T], start: usize, end: usize) -> &[T] {
-    if start >= vec.len() || start >= end {
-        return &[];
-    }
-    let end = end.min(vec.len());
-    &vec[start..end]
-}
-
 #[cfg(test)]
 mod test {
-    use super::solve;
+    use super::{fix_update, solve, InputData, OrderingRules};
 
     const EXAMPLE: [&str; 28] = [
         "47|53",
@@ -196,4 +369,68 @@ mod test {
     fn solve_example_part1() {
         assert_eq!(solve(EXAMPLE.map(String::from).to_vec()), (143, 123));
     }
+
+    #[test]
+    fn fix_updates() {
+        let input_data = InputData::from(EXAMPLE.map(String::from).to_vec());
+        let rules = input_data.get_ordering_rules();
+
+        assert_eq!(
+            fix_update(&[75, 97, 47, 61, 53], rules),
+            Ok(vec![97, 75, 47, 61, 53])
+        );
+        assert_eq!(fix_update(&[61, 13, 29], rules), Ok(vec![61, 29, 13]));
+        assert_eq!(
+            fix_update(&[97, 13, 75, 29, 47], rules),
+            Ok(vec![97, 75, 47, 29, 13])
+        );
+    }
+
+    #[test]
+    fn topological_order_with_missing_rules() {
+        let mut rules = OrderingRules::default();
+        rules.add_rule(1, 2);
+        rules.add_rule(2, 3);
+
+        assert_eq!(fix_update(&[3, 2, 1], &rules), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn cyclic_rules() {
+        let mut rules = OrderingRules::default();
+        rules.add_rule(1, 2);
+        rules.add_rule(2, 3);
+        rules.add_rule(3, 1);
+        rules.add_rule(3, 4);
+        rules.add_rule(5, 6);
+        rules.add_rule(6, 5);
+
+        assert_eq!(
+            rules.cyclic_subsets(&rules.pages()),
+            vec![vec![1, 2, 3], vec![5, 6]]
+        );
+        assert!(rules.cyclic_subsets(&[1, 2, 4]).is_empty());
+
+        let err = fix_update(&[4, 3, 2, 1], &rules).unwrap_err();
+        assert_eq!(err.subsets, vec![vec![1, 2, 3]]);
+        assert_eq!(err.to_string(), "rules are cyclic between pages [1, 2, 3]");
+    }
+
+    #[test]
+    fn cyclic_rules_on_long_update() {
+        // Every page precedes every higher page, except the last which precedes the first
+        let mut rules = OrderingRules::default();
+        for before_page in 0..60 {
+            for after_page in before_page + 1..60 {
+                match (before_page, after_page) {
+                    (0, 59) => rules.add_rule(59, 0),
+                    _ => rules.add_rule(before_page, after_page),
+                }
+            }
+        }
+
+        let update: Vec<u64> = (0..60).rev().collect();
+        let err = fix_update(&update, &rules).unwrap_err();
+        assert_eq!(err.subsets, vec![(0..60).collect::<Vec<u64>>()]);
+    }
 }
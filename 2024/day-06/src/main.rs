@@ -1,6 +1,6 @@
 use std::fs::read_to_string;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Position {
     row: isize,
     col: isize,
@@ -39,7 +39,7 @@ impl Map {
      */
     pub fn walk(&mut self) -> bool {
         let mut direction = Direction::North;
-        let mut position = self.start.clone();
+        let mut position = self.start;
 
         while self.in_bounds(&position) {
             let next_position = direction.next_position(&position);
@@ -78,24 +78,87 @@ impl Map {
         false
     }
 
-    pub fn find_cyclic(&mut self) -> u64 {
-        let mut result = 0;
-        let size = self.size();
-        for row in 0..size.0 {
-            for col in 0..size.1 {
-                if let TileType::Empty { visited_from: _ } = &mut self.matrix[row][col].tile_type {
-                    self.matrix[row][col].tile_type = TileType::Obstruction;
-                    if self.walk() {
-                        result += 1;
-                    }
+    fn tile_at(&self, position: &Position) -> Option<&Tile> {
+        match self.in_bounds(position) {
+            true => Some(&self.matrix[position.row as usize][position.col as usize]),
+            false => None,
+        }
+    }
+
+    /// Distinct positions on the guard's original patrol, in the order they are first visited.
+    ///
+    /// Unlike `walk`, this leaves the tiles untouched.
+    pub fn patrol_positions(&self) -> Vec<Position> {
+        let mut visited = vec![false; self.rows() * self.cols()];
+        let mut seen = vec![false; self.rows() * self.cols() * 4];
+        let mut positions: Vec<Position> = Vec::new();
+
+        let mut direction = Direction::North;
+        let mut position = self.start;
+
+        while self.in_bounds(&position) {
+            let index = position.row as usize * self.cols() + position.col as usize;
+            if seen[index * 4 + direction.index()] {
+                break;
+            }
+            seen[index * 4 + direction.index()] = true;
 
-                    self.matrix[row][col].tile_type = TileType::Empty { visited_from: None };
-                    self.reset();
+            if !visited[index] {
+                visited[index] = true;
+                positions.push(position);
+            }
+
+            let next_position = direction.next_position(&position);
+            match self.tile_at(&next_position) {
+                Some(next_tile) if next_tile.tile_type == TileType::Obstruction => {
+                    direction = direction.next_direction();
                 }
+                _ => position = next_position,
             }
         }
 
-        result
+        positions
+    }
+
+    /// Every position where adding a single obstruction traps the guard in a loop.
+    ///
+    /// Only tiles on the original patrol can change the guard's route, and the guard
+    /// jumps straight between turns using a `JumpTable`.
+    pub fn find_loop_obstructions(&self) -> Vec<Position> {
+        let jumps = JumpTable::from(self);
+        let mut seen: Vec<u32> = vec![0; self.rows() * self.cols() * 4];
+
+        self.patrol_positions()
+            .into_iter()
+            .filter(|position| *position != self.start)
+            .enumerate()
+            .filter(|(attempt, obstruction)| {
+                // Stamp each attempt rather than clearing `seen` every time
+                let stamp = *attempt as u32 + 1;
+                let extra = (obstruction.row as usize, obstruction.col as usize);
+
+                let mut direction = Direction::North;
+                let mut position = (self.start.row as usize, self.start.col as usize);
+
+                while let Some(stop) = jumps.jump(position, direction, Some(extra)) {
+                    let key = (stop.0 * self.cols() + stop.1) * 4 + direction.index();
+                    if seen[key] == stamp {
+                        return true;
+                    }
+                    seen[key] = stamp;
+
+                    position = stop;
+                    direction = direction.next_direction();
+                }
+
+                false
+            })
+            .map(|(_, obstruction)| obstruction)
+            .collect()
+    }
+
+    pub fn find_cyclic(&self) -> u64 {
+        self.find_loop_obstructions().len() as u64
     }
 
     pub fn reset(&mut self) {
@@ -169,6 +232,104 @@ impl From<Vec<String>> for Map {
     }
 }
 
+/// For every tile and direction, where the guard stops when the next obstruction is
+/// reached, or `None` if they walk off the map instead.
+#[derive(Debug)]
+pub struct JumpTable {
+    cols: usize,
+    stops: Vec<[Option<(usize, usize)>; 4]>,
+}
+
+impl JumpTable {
+    /// Where the guard at `position` facing `direction` stops, taking an optional extra
+    /// obstruction into account.
+    pub fn jump(
+        &self,
+        position: (usize, usize),
+        direction: Direction,
+        extra: Option<(usize, usize)>,
+    ) -> Option<(usize, usize)> {
+        let (row, col) = position;
+        let stop = self.stops[row * self.cols + col][direction.index()];
+
+        // The extra obstruction only matters when it is between the guard and the usual stop
+        if let Some((extra_row, extra_col)) = extra {
+            let blocked = match direction {
+                Direction::North => {
+                    extra_col == col && extra_row < row && stop.is_none_or(|s| extra_row >= s.0)
+                }
+                Direction::South => {
+                    extra_col == col && extra_row > row && stop.is_none_or(|s| extra_row <= s.0)
+                }
+                Direction::East => {
+                    extra_row == row && extra_col > col && stop.is_none_or(|s| extra_col <= s.1)
+                }
+                Direction::West => {
+                    extra_row == row && extra_col < col && stop.is_none_or(|s| extra_col >= s.1)
+                }
+            };
+
+            if blocked {
+                return Some(match direction {
+                    Direction::North => (extra_row + 1, col),
+                    Direction::South => (extra_row - 1, col),
+                    Direction::East => (row, extra_col - 1),
+                    Direction::West => (row, extra_col + 1),
+                });
+            }
+        }
+
+        stop
+    }
+}
+
+impl From<&Map> for JumpTable {
+    fn from(map: &Map) -> Self {
+        let (rows, cols) = map.size();
+        let mut stops: Vec<[Option<(usize, usize)>; 4]> = vec![[None; 4]; rows * cols];
+        let is_obstruction =
+            |row: usize, col: usize| map.matrix[row][col].tile_type == TileType::Obstruction;
+
+        for col in 0..cols {
+            let mut stop: Option<(usize, usize)> = None;
+            for row in 0..rows {
+                match is_obstruction(row, col) {
+                    true => stop = Some((row + 1, col)),
+                    false => stops[row * cols + col][Direction::North.index()] = stop,
+                }
+            }
+
+            let mut stop: Option<(usize, usize)> = None;
+            for row in (0..rows).rev() {
+                match is_obstruction(row, col) {
+                    true => stop = row.checked_sub(1).map(|row| (row, col)),
+                    false => stops[row * cols + col][Direction::South.index()] = stop,
+                }
+            }
+        }
+
+        for row in 0..rows {
+            let mut stop: Option<(usize, usize)> = None;
+            for col in 0..cols {
+                match is_obstruction(row, col) {
+                    true => stop = Some((row, col + 1)),
+                    false => stops[row * cols + col][Direction::West.index()] = stop,
+                }
+            }
+
+            let mut stop: Option<(usize, usize)> = None;
+            for col in (0..cols).rev() {
+                match is_obstruction(row, col) {
+                    true => stop = col.checked_sub(1).map(|col| (row, col)),
+                    false => stops[row * cols + col][Direction::East.index()] = stop,
+                }
+            }
+        }
+
+        JumpTable { cols, stops }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Direction {
     North,
@@ -178,6 +339,15 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub fn index(&self) -> usize {
+        match self {
+            Direction::North => 0,
+            Direction::East => 1,
+            Direction::South => 2,
+            Direction::West => 3,
+        }
+    }
+
     pub fn next_position(&self, position: &Position) -> Position {
         match self {
             Direction::North => Position {
@@ -251,7 +421,7 @@ fn part1(lines: Vec<String>) -> u64 {
 }
 
 fn part2(lines: Vec<String>) -> u64 {
-    let mut map = Map::from(lines);
+    let map = Map::from(lines);
 
     map.find_cyclic()
 }
@@ -272,7 +442,7 @@ fn read_lines(filename: &str) -> Vec<String> {
 #[cfg(test)]
 mod test {
 
-    use super::{part1, part2};
+    use super::{part1, part2, Map, Position};
 
     const EXAMPLE: [&str; 10] = [
         "....#.....",
@@ -296,4 +466,27 @@ mod test {
     fn solve_part2() {
         assert_eq!(part2(EXAMPLE.map(String::from).to_vec()), 6);
     }
+
+    #[test]
+    fn loop_obstructions() {
+        let map = Map::from(EXAMPLE.map(String::from).to_vec());
+        let mut obstructions: Vec<(isize, isize)> = map
+            .find_loop_obstructions()
+            .iter()
+            .map(|Position { row, col }| (*row, *col))
+            .collect();
+        obstructions.sort();
+
+        assert_eq!(
+            obstructions,
+            vec![(6, 3), (7, 6), (7, 7), (8, 1), (8, 3), (9, 7)]
+        );
+    }
+
+    #[test]
+    fn patrol_positions_match_walk() {
+        let map = Map::from(EXAMPLE.map(String::from).to_vec());
+
+        assert_eq!(map.patrol_positions().len(), 41);
+    }
 }
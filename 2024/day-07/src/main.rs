@@ -1,4 +1,4 @@
-use std::fs::read_to_string;
+use std::{fmt::Display, fs::read_to_string};
 
 #[derive(Debug)]
 struct Equation {
@@ -6,64 +6,147 @@ struct Equation {
     numbers: Vec<u64>,
 }
 
-fn possible_solutions(numbers: &[u64], support_concatenations: bool) -> Vec<u64> {
-    match numbers.len() {
-        0 => vec![],
-        1 => vec![numbers[0]],
-        2 => {
-            let first = numbers[0];
-            let second = numbers[1];
-
-            let mut poss_solutions = vec![first + second, first * second];
-
-            if support_concatenations {
-                poss_solutions.push(
-                    format!("{}{}", numbers[0], numbers[1])
-                        .parse::<u64>()
-                        .unwrap(),
-                );
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Operator {
+    Add,
+    Multiply,
+    Concatenate,
+}
+
+const PART1_OPERATORS: [Operator; 2] = [Operator::Add, Operator::Multiply];
+const PART2_OPERATORS: [Operator; 3] = [Operator::Add, Operator::Multiply, Operator::Concatenate];
+
+impl Operator {
+    /// Apply the operator, or `None` if the result overflows a u64.
+    pub fn apply(&self, lhs: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Operator::Add => lhs.checked_add(rhs),
+            Operator::Multiply => lhs.checked_mul(rhs),
+            Operator::Concatenate => Self::shift(rhs)
+                .and_then(|shift| lhs.checked_mul(shift))
+                .and_then(|shifted| shifted.checked_add(rhs)),
+        }
+    }
+
+    /// The left-hand side that produces `result` when combined with `rhs`, if there is one.
+    pub fn unapply(&self, result: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Operator::Add => result.checked_sub(rhs),
+            Operator::Multiply => match rhs != 0 && result.is_multiple_of(rhs) {
+                true => Some(result / rhs),
+                false => None,
+            },
+            Operator::Concatenate => {
+                let shift = Self::shift(rhs)?;
+                match result % shift == rhs {
+                    true => Some(result / shift),
+                    false => None,
+                }
             }
+        }
+    }
 
-            poss_solutions
+    pub fn symbol(&self) -> &str {
+        match self {
+            Operator::Add => "+",
+            Operator::Multiply => "*",
+            Operator::Concatenate => "||",
         }
-        _ => {
-            let first = numbers[0];
-            let second = numbers[1];
-            let rest = numbers[2..].to_vec();
-
-            let mut added = vec![first + second];
-            added.extend(&rest);
-
-            let mut multiplied = vec![first * second];
-            multiplied.extend(&rest);
-
-            let mut poss_solutions = [
-                possible_solutions(&added, support_concatenations),
-                possible_solutions(&multiplied, support_concatenations),
-            ]
-            .to_vec();
-
-            if support_concatenations {
-                let mut concatentated =
-                    vec![format!("{}{}", first, second).parse::<u64>().unwrap()];
-                concatentated.extend(&rest);
-                poss_solutions.push(possible_solutions(&concatentated, support_concatenations));
+    }
+
+    // Power of 10 that moves a number left by as many digits as `value` has, if it fits
+    fn shift(value: u64) -> Option<u64> {
+        10_u64.checked_pow(value.checked_ilog10().unwrap_or(0) + 1)
+    }
+}
+
+/// Numbers combined left to right by operators, ignoring precedence.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Expression {
+    numbers: Vec<u64>,
+    operators: Vec<Operator>,
+}
+
+impl Expression {
+    pub fn evaluate(&self) -> Option<u64> {
+        self.operators
+            .iter()
+            .zip(self.numbers.iter().skip(1))
+            .try_fold(self.numbers[0], |total, (operator, number)| {
+                operator.apply(total, *number)
+            })
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.numbers[0])?;
+        for (operator, number) in self.operators.iter().zip(self.numbers.iter().skip(1)) {
+            write!(f, " {} {number}", operator.symbol())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Operators combining `numbers` into `target`, working backwards from the last number
+/// so only operators that could have produced the target are explored.
+fn find_operators(target: u64, numbers: &[u64], operators: &[Operator]) -> Option<Vec<Operator>> {
+    match numbers {
+        [] => None,
+        [first] => match *first == target {
+            true => Some(vec![]),
+            false => None,
+        },
+        [rest @ .., last] => {
+            // Anything times zero is zero, so any combination of the rest will do
+            if target == 0 && *last == 0 && operators.contains(&Operator::Multiply) {
+                if let Some(mut found) = any_operators(rest[0], &rest[1..], operators) {
+                    found.push(Operator::Multiply);
+                    return Some(found);
+                }
             }
 
-            poss_solutions.concat()
+            operators.iter().find_map(|operator| {
+                let lhs = operator.unapply(target, *last)?;
+                let mut found = find_operators(lhs, rest, operators)?;
+                found.push(*operator);
+                Some(found)
+            })
         }
     }
 }
 
+/// Any operators combining `numbers` onto `total` without overflowing.
+fn any_operators(total: u64, numbers: &[u64], operators: &[Operator]) -> Option<Vec<Operator>> {
+    match numbers {
+        [] => Some(vec![]),
+        [next, rest @ ..] => operators.iter().find_map(|operator| {
+            let total = operator.apply(total, *next)?;
+            let mut found = any_operators(total, rest, operators)?;
+            found.insert(0, *operator);
+            Some(found)
+        }),
+    }
+}
+
 impl Equation {
     pub fn value(&self) -> u64 {
         self.value
     }
 
-    pub fn solvable(&self, support_concatenations: bool) -> bool {
-        possible_solutions(&self.numbers, support_concatenations)
-            .iter()
-            .any(|solution| solution == &self.value)
+    /// One expression of the numbers that evaluates to the test value, if any exists.
+    pub fn solve(&self, operators: &[Operator]) -> Option<Expression> {
+        let expression = Expression {
+            numbers: self.numbers.clone(),
+            operators: find_operators(self.value, &self.numbers, operators)?,
+        };
+
+        // Guard against overflow in the parts of the expression not checked going backwards
+        match expression.evaluate() == Some(self.value) {
+            true => Some(expression),
+            false => None,
+        }
     }
 }
 
@@ -72,7 +155,7 @@ impl From<&String> for Equation {
         let split = s.split(": ").collect::<Vec<_>>();
         let value = split[0].parse::<u64>().unwrap();
         let numbers = split[1]
-            .split(" ")
+            .split(' ')
             .map(|s| s.parse::<u64>().unwrap())
             .collect();
 
@@ -85,7 +168,7 @@ fn part1(lines: Vec<String>) -> u64 {
 
     let equations = lines.iter().map(Equation::from).collect::<Vec<_>>();
     for equation in equations {
-        if equation.solvable(false) {
+        if equation.solve(&PART1_OPERATORS).is_some() {
             result += equation.value();
         }
     }
@@ -98,7 +181,7 @@ fn part2(lines: Vec<String>) -> u64 {
 
     let equations = lines.iter().map(Equation::from).collect::<Vec<_>>();
     for equation in equations {
-        if equation.solvable(true) {
+        if equation.solve(&PART2_OPERATORS).is_some() {
             result += equation.value();
         }
     }
@@ -123,6 +206,7 @@ fn read_lines(filename: &str) -> Vec<String> {
 mod test {
     use super::part1;
     use super::part2;
+    use super::{Equation, Operator, PART1_OPERATORS, PART2_OPERATORS};
 
     const EXAMPLE: [&str; 9] = [
         "190: 10 19",
@@ -145,4 +229,72 @@ mod test {
     fn solve_example_part2() {
         assert_eq!(part2(EXAMPLE.map(String::from).to_vec()), 11387);
     }
+
+    #[test]
+    fn witness_expressions() {
+        let equation = Equation::from(&EXAMPLE[1].to_string());
+        let expression = equation.solve(&PART1_OPERATORS).unwrap();
+        assert_eq!(expression.to_string(), "81 * 40 + 27");
+        assert_eq!(expression.evaluate(), Some(3267));
+
+        let equation = Equation::from(&EXAMPLE[3].to_string());
+        assert!(equation.solve(&PART1_OPERATORS).is_none());
+        assert_eq!(
+            equation.solve(&PART2_OPERATORS).unwrap().to_string(),
+            "15 || 6"
+        );
+
+        let equation = Equation::from(&EXAMPLE[4].to_string());
+        assert_eq!(
+            equation.solve(&PART2_OPERATORS).unwrap().to_string(),
+            "6 * 8 || 6 * 15"
+        );
+    }
+
+    #[test]
+    fn custom_operators() {
+        let equation = Equation::from(&"190: 10 19".to_string());
+
+        assert!(equation.solve(&[Operator::Add]).is_none());
+        assert!(equation.solve(&[Operator::Multiply]).is_some());
+    }
+
+    #[test]
+    fn zeroes() {
+        let equation = Equation::from(&"0: 5 7 0".to_string());
+        assert_eq!(
+            equation.solve(&PART1_OPERATORS).unwrap().to_string(),
+            "5 + 7 * 0"
+        );
+
+        let equation = Equation::from(&"50: 5 0".to_string());
+        assert_eq!(
+            equation.solve(&PART2_OPERATORS).unwrap().to_string(),
+            "5 || 0"
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(Operator::Concatenate.apply(1, u64::MAX), None);
+        assert_eq!(Operator::Concatenate.unapply(5, u64::MAX), None);
+        let equation = Equation::from(&"5: 1 18446744073709551615".to_string());
+        assert!(equation.solve(&PART2_OPERATORS).is_none());
+
+        assert_eq!(Operator::Multiply.apply(u64::MAX, 2), None);
+        assert_eq!(Operator::Concatenate.apply(u64::MAX / 10, 99), None);
+
+        let equation = Equation::from(&"0: 18446744073709551615 1 0".to_string());
+        assert_eq!(
+            equation.solve(&PART1_OPERATORS).unwrap().to_string(),
+            "18446744073709551615 * 1 * 0"
+        );
+        assert!(equation.solve(&[Operator::Add]).is_none());
+
+        let equation = Equation::from(&"18446744073709551615: 18446744073709551615 1".to_string());
+        assert_eq!(
+            equation.solve(&PART2_OPERATORS).unwrap().to_string(),
+            "18446744073709551615 * 1"
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use std::fs::read_to_string;
+use std::{collections::BTreeSet, fs::read_to_string};
 
 #[derive(Debug)]
 struct Range {
@@ -16,45 +16,75 @@ impl From<&str> for Range {
     }
 }
 
-fn part1(ranges: Vec<Range>) -> usize {
-    ranges.into_iter().fold(0, |mut acc, range| {
-        let mut current = range.start;
-        while current <= range.end {
-            let s = current.to_string();
-            let (lhs, rhs) = s.split_at(s.len() / 2);
-            if lhs.eq(rhs) {
-                acc += current;
-            }
+/// How many times a block of digits must repeat to form an invalid ID.
+#[derive(Debug, Clone, Copy)]
+enum Repeats {
+    Exactly(u32),
+    AtLeast(u32),
+}
 
-            current += 1;
+impl Repeats {
+    fn allows(&self, count: u32) -> bool {
+        match self {
+            Repeats::Exactly(repeats) => count == *repeats,
+            Repeats::AtLeast(repeats) => count >= *repeats,
         }
+    }
+}
 
-        acc
-    })
+fn num_digits(value: usize) -> u32 {
+    value.checked_ilog10().unwrap_or(0) + 1
 }
 
-fn part2(ranges: Vec<Range>) -> usize {
-    ranges.into_iter().fold(0, |mut acc, range| {
-        let mut current = range.start;
-        while current <= range.end {
-            let s = current.to_string();
-            let chunks = s.len() / 2 + 1;
-            let chars: Vec<_> = s.chars().collect();
-
-            for i in 1..chunks {
-                let windows: Vec<_> = chars.chunks(i).collect();
-                let first = windows[0];
-                if windows.iter().all(|w| first == *w) {
-                    acc += current;
-                    break;
-                }
+/// Every number in `range` made of a block of digits repeated, built directly rather
+/// than by checking each number in the range.
+///
+/// A `len` digit number repeating a `block` digit block is the block times
+/// `(10^len - 1) / (10^block - 1)`, e.g. 123123 = 123 * 1001, which is built up one block
+/// at a time as `10^len` itself may not fit in a `usize`. The same number can come
+/// from several block sizes, like 1111 from 1 * 1111 and 11 * 101, so they are collected
+/// into a set.
+fn repeated_numbers(range: &Range, repeats: Repeats) -> BTreeSet<usize> {
+    let mut numbers = BTreeSet::new();
+
+    for len in num_digits(range.start)..=num_digits(range.end) {
+        let lo = range.start.max(10_usize.pow(len - 1));
+        let hi = match 10_usize.checked_pow(len) {
+            Some(limit) => range.end.min(limit - 1),
+            None => range.end,
+        };
+
+        for block in (1..len).filter(|block| len.is_multiple_of(*block)) {
+            if !repeats.allows(len / block) {
+                continue;
             }
 
-            current += 1;
+            let multiplier: usize = (0..len / block)
+                .map(|repeat| 10_usize.pow(repeat * block))
+                .sum();
+            let first = lo.div_ceil(multiplier).max(10_usize.pow(block - 1));
+            let last = (hi / multiplier).min(10_usize.pow(block) - 1);
+
+            numbers.extend((first..=last).map(|value| value * multiplier));
         }
+    }
+
+    numbers
+}
+
+fn sum_repeated(ranges: &[Range], repeats: Repeats) -> usize {
+    ranges
+        .iter()
+        .map(|range| repeated_numbers(range, repeats).iter().sum::<usize>())
+        .sum()
+}
+
+fn part1(ranges: Vec<Range>) -> usize {
+    sum_repeated(&ranges, Repeats::Exactly(2))
+}
 
-        acc
-    })
+fn part2(ranges: Vec<Range>) -> usize {
+    sum_repeated(&ranges, Repeats::AtLeast(2))
 }
 
 fn main() {
@@ -70,14 +100,12 @@ fn read_input(filename: &str) -> Vec<Range> {
 }
 
 fn parse_input(input: &str) -> Vec<Range> {
-    input.trim().split(",").map(Range::from).collect()
+    input.trim().split(',').map(Range::from).collect()
 }
 
 #[cfg(test)]
 mod test {
-    use super::parse_input;
-    use super::part1;
-    use super::part2;
+    use super::{Range, Repeats, parse_input, part1, part2, repeated_numbers};
 
     const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
 
@@ -92,4 +120,44 @@ mod test {
         let ranges = parse_input(EXAMPLE);
         assert_eq!(part2(ranges), 4174379265);
     }
+
+    #[test]
+    fn repeated_numbers_deduplicated() {
+        let range = Range::from("1000-1111");
+        let numbers: Vec<usize> = repeated_numbers(&range, Repeats::AtLeast(2))
+            .into_iter()
+            .collect();
+
+        assert_eq!(numbers, vec![1010, 1111]);
+    }
+
+    #[test]
+    fn repeated_numbers_across_lengths() {
+        let range = Range::from("95-1012");
+
+        assert_eq!(
+            repeated_numbers(&range, Repeats::Exactly(2)),
+            [99, 1010].into()
+        );
+        assert_eq!(
+            repeated_numbers(&range, Repeats::AtLeast(2)),
+            [99, 111, 222, 333, 444, 555, 666, 777, 888, 999, 1010].into()
+        );
+    }
+
+    #[test]
+    fn repeated_numbers_up_to_twenty_digits() {
+        let range = Range::from("11111111111111111110-11111111111111111112");
+        assert_eq!(
+            repeated_numbers(&range, Repeats::AtLeast(2)),
+            [11111111111111111111].into()
+        );
+        assert_eq!(
+            repeated_numbers(&range, Repeats::Exactly(2)),
+            [11111111111111111111].into()
+        );
+
+        let range = Range::from("18446744073709551000-18446744073709551615");
+        assert!(repeated_numbers(&range, Repeats::AtLeast(2)).is_empty());
+    }
 }
@@ -1,26 +1,6 @@
-use rust_aoc_utils::grid;
-use rust_aoc_utils::read_lines_from_file;
+use rust_aoc_utils::{columns::parse_columns, read_lines_from_file};
 
-#[derive(Debug, Clone)]
-enum Element {
-    Add,
-    Multiply,
-    Number(usize),
-}
-
-#[derive(Debug, Clone)]
-struct Operation {
-    operator: Operator,
-    size: usize,
-}
-
-impl Operation {
-    pub fn calculate(&self, values: &[usize]) -> usize {
-        self.operator.calculate(values)
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Operator {
     Add,
     Multiply,
@@ -48,108 +28,119 @@ impl Operator {
     }
 }
 
-impl From<&str> for Element {
-    fn from(s: &str) -> Self {
-        match s {
-            "+" => Element::Add,
-            "*" => Element::Multiply,
-            _ => Element::Number(s.parse::<usize>().expect("Expected number")),
+impl TryFrom<char> for Operator {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '+' => Ok(Operator::Add),
+            '*' => Ok(Operator::Multiply),
+            _ => Err(format!("Invalid operator '{value}'")),
         }
     }
 }
 
-fn part1(lines: Vec<String>) -> usize {
-    let grid = grid::transpose(grid::parse_whitespaced(&lines, |s| Element::from(s)));
-
-    let mut result = 0;
-    for row in grid.iter() {
-        let mut iter = row.iter().rev();
-        let operator = iter.next().unwrap();
-        result += match operator {
-            Element::Add => iter.fold(0, |acc, el| match el {
-                Element::Number(num) => acc + num,
-                _ => {
-                    unreachable!("Expected number, but found {:?}", el)
-                }
-            }),
-            Element::Multiply => iter.fold(1, |acc, el| match el {
-                Element::Number(num) => acc * num,
-                _ => {
-                    unreachable!("Expected number, but found {:?}", el)
-                }
-            }),
-            Element::Number(num) => {
-                unreachable!("Expected operator, but found number {}", num);
-            }
-        };
-    }
+/// A single problem on the worksheet: the block of characters above its operator,
+/// padded with spaces to a rectangle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Problem {
+    operator: Operator,
+    cells: Vec<Vec<char>>,
+}
+
+impl Problem {
+    /// Numbers written top to bottom, one per column, read from the rightmost column.
+    fn column_values(&self) -> Vec<usize> {
+        let width = self.cells.first().map_or(0, Vec::len);
 
-    result
+        (0..width)
+            .rev()
+            .filter_map(|col| parse_digits(self.cells.iter().map(|row| &row[col])))
+            .collect()
+    }
 }
 
-fn part2(lines: Vec<String>) -> usize {
-    let mut reverse = lines.to_vec();
-    reverse.reverse();
-    let mut iter = reverse.into_iter();
-
-    let ops_str = iter.next().unwrap();
-
-    let mut operations = vec![];
-    let mut current_size = 0;
-    let mut current_operator: Option<Operator> = None;
-    for char in ops_str.chars() {
-        match char {
-            '+' | '*' => {
-                if let Some(operator) = current_operator {
-                    operations.push(Operation {
-                        operator,
-                        size: current_size,
-                    });
-                    current_size = 0;
-                }
-
-                current_operator = Some(match char {
-                    '+' => Operator::Add,
-                    '*' => Operator::Multiply,
-                    _ => unreachable!(),
-                });
-            }
-            _ => {
-                current_size += 1;
-            }
-        }
+fn parse_digits<'a>(chars: impl Iterator<Item = &'a char>) -> Option<usize> {
+    let digits: String = chars.filter(|ch| !ch.is_whitespace()).collect();
+    match digits.is_empty() {
+        true => None,
+        false => Some(
+            digits
+                .parse::<usize>()
+                .unwrap_or_else(|err| panic!("Invalid number '{digits}': {err}")),
+        ),
     }
+}
 
-    // Capture last Operation
-    operations.push(Operation {
-        operator: current_operator.expect("Missing final operator"),
-        size: current_size + 1,
-    });
-
-    let values = grid::transpose(grid::parse_to_chars(&iter.collect::<Vec<_>>()))
-        .into_iter()
-        .filter_map(|mut row| {
-            row.reverse();
-            let s: String = row.into_iter().collect();
-            s.trim().parse::<usize>().ok()
+/// Problems laid out side by side, separated by columns that are blank in every row.
+///
+/// Lines may be ragged, they are padded with spaces to the longest line.
+fn parse_worksheet(lines: &[String]) -> Vec<Problem> {
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let grid: Vec<Vec<char>> = lines
+        .iter()
+        .map(|line| {
+            let mut row: Vec<char> = line.chars().collect();
+            row.resize(width, ' ');
+            row
         })
-        .collect::<Vec<_>>();
-
-    let mut result = 0;
-    let mut values_iter = values.into_iter();
-    for operation in operations.into_iter() {
-        let mut op_values = vec![];
-        for _ in 0..operation.size {
-            op_values.push(values_iter.next().unwrap());
+        .collect();
+
+    let Some((operators, numbers)) = grid.split_last() else {
+        return Vec::new();
+    };
+
+    let is_blank = |col: usize| grid.iter().all(|row| row[col].is_whitespace());
+    let mut problems = Vec::new();
+    let mut start = 0;
+    for end in (0..=width).filter(|col| *col == width || is_blank(*col)) {
+        if start < end {
+            let mut symbols = operators[start..end]
+                .iter()
+                .filter(|ch| !ch.is_whitespace());
+            let operator = match (symbols.next(), symbols.next()) {
+                (Some(symbol), None) => Operator::try_from(*symbol).unwrap_or_else(|err| {
+                    panic!("Invalid problem at columns {start}..{end}: {err}")
+                }),
+                _ => panic!("Expected one operator at columns {start}..{end}"),
+            };
+
+            problems.push(Problem {
+                operator,
+                cells: numbers.iter().map(|row| row[start..end].to_vec()).collect(),
+            });
         }
-
-        result += operation.calculate(&op_values);
+        start = end + 1;
     }
 
-    result
+    problems
+}
+
+/// Numbers written left to right, read as one whitespace-separated column per problem.
+fn part1(lines: Vec<String>) -> usize {
+    let problems = parse_worksheet(&lines);
+    let numbers = &lines[..lines.len().saturating_sub(1)];
+    let columns: Vec<Vec<usize>> = parse_columns(numbers, problems.len())
+        .unwrap_or_else(|err| panic!("Invalid worksheet: {err}"));
+
+    problems
+        .iter()
+        .zip(columns.iter())
+        .map(|(problem, values)| problem.operator.calculate(values))
+        .sum()
+}
+
+fn part2(lines: Vec<String>) -> usize {
+    parse_worksheet(&lines)
+        .iter()
+        .map(|problem| problem.operator.calculate(&problem.column_values()))
+        .sum()
 }
 
-// NOTE: input.txt needs to be 'right padded' to create a uniform grid!
 fn main() {
     println!(
         "Solution for part 1 is {}",
@@ -163,8 +154,7 @@ fn main() {
 
 #[cfg(test)]
 mod test {
-    use super::part1;
-    use super::part2;
+    use super::{Operator, parse_worksheet, part1, part2};
 
     const EXAMPLE: [&str; 4] = [
         "123 328  51 64 ",
@@ -195,4 +185,38 @@ mod test {
     fn solve_example2_part2() {
         assert_eq!(part2(EXAMPLE_2.map(String::from).to_vec()), 25161998);
     }
+
+    #[test]
+    fn ragged_lines() {
+        let lines = [
+            "123 328  51 64",
+            " 45 64  387 23",
+            "  6 98  215 314",
+            "*   +   *   +",
+        ];
+        let lines = lines.map(String::from).to_vec();
+
+        assert_eq!(part1(lines.clone()), 4277556);
+        assert_eq!(part2(lines), 3263827);
+    }
+
+    #[test]
+    fn tab_separated_problems() {
+        let lines = ["1\t2", "3\t4", "+\t*"].map(String::from).to_vec();
+
+        assert_eq!(parse_worksheet(&lines).len(), 2);
+        assert_eq!(part1(lines.clone()), 12);
+        assert_eq!(part2(lines), 13 + 24);
+    }
+
+    #[test]
+    fn problem_values() {
+        let problems = parse_worksheet(&EXAMPLE.map(String::from));
+
+        assert_eq!(problems.len(), 4);
+        assert_eq!(problems[0].operator, Operator::Multiply);
+        assert_eq!(problems[0].column_values(), vec![356, 24, 1]);
+        assert_eq!(problems[3].operator, Operator::Add);
+        assert_eq!(problems[3].column_values(), vec![4, 431, 623]);
+    }
 }
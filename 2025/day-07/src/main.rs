@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::fmt::Display;
 
 use rust_aoc_utils::grid;
@@ -9,7 +8,6 @@ enum Element {
     Start,
     Splitter,
     Space,
-    Beam,
 }
 
 impl From<char> for Element {
@@ -29,125 +27,121 @@ impl Display for Element {
             Element::Start => write!(f, "S"),
             Element::Splitter => write!(f, "^"),
             Element::Space => write!(f, "."),
-            Element::Beam => write!(f, "|"),
         }
     }
 }
 
-fn map_beams(grid: &mut [Vec<Element>]) -> usize {
-    let mut result = 0;
-    for row in 0..grid.len() {
-        for col in 0..grid[row].len() {
-            let next_row = row + 1;
-            let next_col = col + 1;
-
-            match grid[row][col] {
-                Element::Start => {
-                    if next_row < grid.len() {
-                        grid[next_row][col] = Element::Beam;
-                    }
-                }
-                Element::Beam => {
-                    if next_row == grid.len() {
-                        continue;
-                    }
+/// Beams followed down the manifold one row at a time, with the number of timelines
+/// passing through every cell.
+#[derive(Debug, Clone)]
+struct Sweep {
+    grid: Vec<Vec<Element>>,
+    /// Timelines entering each cell, row by row
+    counts: Vec<Vec<u128>>,
+    /// Splitters reached by at least one beam
+    splits: usize,
+}
 
-                    match grid[next_row][col] {
-                        Element::Splitter => {
-                            if col > 0 && grid[next_row][col - 1] == Element::Space {
-                                grid[next_row][col - 1] = Element::Beam;
-                            }
-                            if next_col < grid[row].len()
-                                && grid[next_row][next_col] == Element::Space
-                            {
-                                grid[next_row][next_col] = Element::Beam;
-                            }
-                            result += 1;
+impl Sweep {
+    fn new(grid: Vec<Vec<Element>>) -> Self {
+        let width = grid.first().expect("Grid is empty").len();
+        let mut counts: Vec<Vec<u128>> = Vec::with_capacity(grid.len());
+        let mut splits = 0;
+
+        let start_col = grid[0]
+            .iter()
+            .position(|el| el == &Element::Start)
+            .expect("Could not find S in first row");
+        let mut current = vec![0_u128; width];
+        current[start_col] = 1;
+
+        for row in grid.iter() {
+            let mut next = vec![0_u128; width];
+            for (col, count) in current.iter().enumerate().filter(|(_, count)| **count > 0) {
+                match row[col] {
+                    Element::Splitter => {
+                        splits += 1;
+                        // Beams split off either side of the manifold are lost
+                        if col > 0 {
+                            next[col - 1] += count;
                         }
-                        Element::Space => {
-                            grid[next_row][col] = Element::Beam;
+                        if col + 1 < width {
+                            next[col + 1] += count;
                         }
-                        _ => {}
                     }
+                    _ => next[col] += count,
                 }
-                _ => {}
             }
+
+            counts.push(std::mem::replace(&mut current, next));
+        }
+        counts.push(current);
+
+        Sweep {
+            grid,
+            counts,
+            splits,
         }
     }
 
-    result
-}
+    fn splits(&self) -> usize {
+        self.splits
+    }
 
-fn part1(lines: Vec<String>) -> usize {
-    let mut grid = grid::parse(&lines, Element::from);
+    /// Timelines that leave the bottom of the manifold.
+    fn timelines(&self) -> u128 {
+        self.counts.last().unwrap().iter().sum()
+    }
 
-    let result = map_beams(&mut grid);
-    grid::print(&grid);
+    /// A log-scale view of the manifold, with every cell a beam reaches replaced by the
+    /// number of digits in its timeline count, written in base 36.
+    ///
+    /// Counts can reach 39 digits, so anything past 35 digits is shown as `z`.
+    fn heatmap(&self) -> String {
+        let mut heatmap = String::new();
+        for (row, counts) in self.grid.iter().zip(self.counts.iter()) {
+            for (el, count) in row.iter().zip(counts.iter()) {
+                let digits = count.checked_ilog10().map(|log| (log + 1).min(35));
+                match (el, digits) {
+                    (Element::Space, Some(digits)) => {
+                        heatmap.push(char::from_digit(digits, 36).unwrap())
+                    }
+                    _ => heatmap.push_str(&el.to_string()),
+                }
+            }
+            heatmap.push('\n');
+        }
 
-    result
+        heatmap
+    }
 }
 
-fn part2(lines: Vec<String>) -> usize {
-    let grid = grid::parse(&lines, Element::from);
-
-    let start_col = grid
-        .first()
-        .expect("Grid is empty")
-        .iter()
-        .position(|el| el == &Element::Start)
-        .expect("Could not find S in first row");
+fn sweep(lines: &[String]) -> Sweep {
+    Sweep::new(grid::parse(lines, Element::from))
+}
 
-    let mut cache = HashMap::new();
+fn part1(sweep: &Sweep) -> usize {
+    sweep.splits()
+}
 
-    find_timelines(&grid, 1, start_col, &mut cache)
+fn part2(sweep: &Sweep) -> u128 {
+    sweep.timelines()
 }
 
-fn find_timelines(
-    grid: &[Vec<Element>],
-    row: usize,
-    beam_col: usize,
-    cache: &mut HashMap<(usize, usize), usize>,
-) -> usize {
-    if row == grid.len() {
-        return 1;
-    }
+fn main() {
+    let sweep = sweep(&read_lines_from_file("input.txt"));
 
-    let el = &grid[row][beam_col];
-    match el {
-        Element::Splitter => {
-            let left = match cache.get(&(row + 2, beam_col - 1)) {
-                Some(val) => *val,
-                None => find_timelines(grid, row + 2, beam_col - 1, cache),
-            };
-
-            let right = match cache.get(&(row + 1, beam_col + 1)) {
-                Some(val) => *val,
-                None => find_timelines(grid, row + 1, beam_col + 1, cache),
-            };
-
-            let result = left + right;
-            cache.insert((row, beam_col), result);
-            result
-        }
-        _ => find_timelines(grid, row + 1, beam_col, cache),
+    if std::env::args().any(|arg| arg == "--heatmap") {
+        print!("{}", sweep.heatmap());
     }
-}
 
-fn main() {
-    println!(
-        "Solution for part 1 is {}",
-        part1(read_lines_from_file("input.txt"))
-    );
-    println!(
-        "Solution for part 2 is {}",
-        part2(read_lines_from_file("input.txt"))
-    );
+    println!("Solution for part 1 is {}", part1(&sweep));
+    println!("Solution for part 2 is {}", part2(&sweep));
 }
 
 #[cfg(test)]
 mod test {
-    use super::part1;
-    use super::part2;
+    use super::{part1, part2, sweep};
 
     const EXAMPLE: [&str; 16] = [
         ".......S.......",
@@ -170,11 +164,52 @@ mod test {
 
     #[test]
     fn solve_example_part1() {
-        assert_eq!(part1(EXAMPLE.map(String::from).to_vec()), 21);
+        assert_eq!(part1(&sweep(&EXAMPLE.map(String::from))), 21);
     }
 
     #[test]
     fn solve_example_part2() {
-        assert_eq!(part2(EXAMPLE.map(String::from).to_vec()), 40);
+        assert_eq!(part2(&sweep(&EXAMPLE.map(String::from))), 40);
+    }
+
+    #[test]
+    fn sweep_heatmap() {
+        let sweep = sweep(&EXAMPLE.map(String::from));
+        let heatmap = sweep.heatmap();
+        let rows: Vec<&str> = heatmap.lines().collect();
+
+        assert_eq!(sweep.splits(), 21);
+        assert_eq!(sweep.timelines(), 40);
+        assert_eq!(rows[0], ".......S.......");
+        assert_eq!(rows[1], ".......1.......");
+        assert_eq!(rows[3], "......1.1......");
+        assert_eq!(rows[15], "1.1.2.2.2.111.1");
+    }
+
+    #[test]
+    fn splits_are_not_repeated() {
+        // Two beams meeting on the same splitter only count it once
+        let lines = ["..S..", "..^..", ".^.^.", "..^..", "....."].map(String::from);
+        let sweep = sweep(&lines);
+
+        assert_eq!(sweep.splits(), 4);
+        assert_eq!(sweep.timelines(), 6);
+    }
+
+    #[test]
+    fn heatmap_clamps_long_counts() {
+        // Each row of splitters doubles the timelines, giving well over 35 digits
+        let mut lines = vec![format!("{}S{}", ".".repeat(200), ".".repeat(200))];
+        for row in 0..125 {
+            match row % 2 {
+                0 => lines.push("^.".repeat(200) + "^"),
+                _ => lines.push(".^".repeat(200) + "."),
+            }
+        }
+        lines.push(".".repeat(401));
+        let sweep = sweep(&lines);
+
+        assert!(sweep.timelines() > 10_u128.pow(36));
+        assert!(sweep.heatmap().contains('z'));
     }
 }
@@ -1,15 +1,34 @@
-use std::rc::Rc;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use std::str::FromStr;
 
-use itertools::Itertools;
 use rust_aoc_utils::read_lines_from_file;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Point {
     x: isize,
     y: isize,
     z: isize,
-    circuit: Option<Rc<Circuit>>,
+}
+
+impl Point {
+    fn axis(&self, axis: usize) -> isize {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+
+    /// Squared distance, which keeps pairs in exact order without taking a root.
+    fn distance_sq(&self, other: &Point) -> i64 {
+        let (dx, dy, dz) = (
+            (other.x - self.x) as i64,
+            (other.y - self.y) as i64,
+            (other.z - self.z) as i64,
+        );
+        dx * dx + dy * dy + dz * dz
+    }
 }
 
 impl FromStr for Point {
@@ -20,12 +39,7 @@ impl FromStr for Point {
         let x = parts.next().unwrap().parse().unwrap();
         let y = parts.next().unwrap().parse().unwrap();
         let z = parts.next().unwrap().parse().unwrap();
-        Ok(Point {
-            x,
-            y,
-            z,
-            circuit: None,
-        })
+        Ok(Point { x, y, z })
     }
 }
 
@@ -35,182 +49,252 @@ impl From<&String> for Point {
     }
 }
 
-#[derive(Debug, Clone)]
-struct Edge {
-    a_index: usize,
-    b_index: usize,
-    distance: isize,
+/// Two junction boxes, by index, with `a < b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pair {
+    a: usize,
+    b: usize,
+    distance_sq: i64,
 }
 
-impl Edge {
-    fn new(a_index: usize, b_index: usize, points: &[Point]) -> Self {
-        let a = &points[a_index];
-        let b = &points[b_index];
-        Edge {
-            a_index,
-            b_index,
-            distance: ((b.x - a.x).pow(2) + (b.y - a.y).pow(2) + (b.z - a.z).pow(2)).isqrt(),
-        }
-    }
+/// A k-d tree over the points, stored as an ordering of point indices where each node is
+/// the median of its slice along the axis for its depth.
+struct KdTree<'a> {
+    points: &'a [Point],
+    order: Vec<usize>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Circuit {}
+impl<'a> KdTree<'a> {
+    fn new(points: &'a [Point]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        Self::build(points, &mut order, 0);
+        KdTree { points, order }
+    }
 
-/// A wrapper around points and edges that ensures index stability.
-/// Once created, the points cannot be reordered, but their data can be modified.
-struct PointGraph {
-    points: Vec<Point>,
-    edges: Vec<Edge>,
-}
+    fn build(points: &[Point], order: &mut [usize], depth: usize) {
+        if order.len() <= 1 {
+            return;
+        }
+
+        let mid = order.len() / 2;
+        order.select_nth_unstable_by_key(mid, |index| points[*index].axis(depth % 3));
 
-impl PointGraph {
-    fn new(points: Vec<Point>) -> Self {
-        let edges = Self::create_edges(&points);
-        PointGraph { points, edges }
+        let (below, above) = order.split_at_mut(mid);
+        Self::build(points, below, depth + 1);
+        Self::build(points, &mut above[1..], depth + 1);
     }
 
-    fn create_edges(points: &[Point]) -> Vec<Edge> {
-        let mut edges = (0..points.len())
-            .tuple_combinations()
-            .map(|(i, j)| Edge::new(i, j, points))
-            .collect::<Vec<_>>();
+    /// The `k` points closest to `target`, excluding itself, ordered by distance and then
+    /// index so that a larger `k` always extends a smaller one.
+    fn nearest(&self, target: usize, k: usize) -> Vec<(i64, usize)> {
+        let mut best: BinaryHeap<(i64, usize)> = BinaryHeap::with_capacity(k + 1);
+        self.search(0, self.order.len(), 0, target, k, &mut best);
 
-        edges.sort_by_key(|e| e.distance);
-        edges
+        best.into_sorted_vec()
     }
 
-    fn edges(&self) -> &[Edge] {
-        &self.edges
-    }
+    fn search(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        target: usize,
+        k: usize,
+        best: &mut BinaryHeap<(i64, usize)>,
+    ) {
+        if lo >= hi || k == 0 {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let node = self.order[mid];
+        let point = &self.points[target];
 
-    /// Returns a map of circuit pointer to point count
-    pub fn circuit_counts(&self) -> std::collections::HashMap<*const Circuit, usize> {
-        let mut circuit_counts = std::collections::HashMap::new();
-        for point in &self.points {
-            if let Some(ref circuit) = point.circuit {
-                *circuit_counts.entry(Rc::as_ptr(circuit)).or_insert(0) += 1;
+        if node != target {
+            best.push((point.distance_sq(&self.points[node]), node));
+            if best.len() > k {
+                best.pop();
             }
         }
-        circuit_counts
-    }
 
-    /// Returns the sizes of all circuits, sorted largest first
-    pub fn circuit_sizes(&self) -> Vec<usize> {
-        let mut counts: Vec<usize> = self.circuit_counts().values().copied().collect();
-        counts.sort_by(|a, b| b.cmp(a));
-        counts
+        let axis = depth % 3;
+        let diff = (point.axis(axis) - self.points[node].axis(axis)) as i64;
+        let (near, far) = match diff < 0 {
+            true => ((lo, mid), (mid + 1, hi)),
+            false => ((mid + 1, hi), (lo, mid)),
+        };
+
+        self.search(near.0, near.1, depth + 1, target, k, best);
+        // Ties still need a look, since a lower index wins at the same distance
+        if best.len() < k || diff * diff <= best.peek().unwrap().0 {
+            self.search(far.0, far.1, depth + 1, target, k, best);
+        }
     }
+}
 
-    /// Returns the total number of points that have circuits
-    #[allow(dead_code)]
-    pub fn connected_points_count(&self) -> usize {
-        self.points.iter().filter(|p| p.circuit.is_some()).count()
-    }
+/// Streams every pair of points in order of exact squared distance.
+///
+/// Each point keeps a buffer of its nearest neighbors, doubled whenever it runs out, and
+/// a heap holds the next unused pair for every point, so only as many pairs are found as
+/// are consumed.
+struct ClosestPairs<'a> {
+    tree: KdTree<'a>,
+    neighbors: Vec<Vec<(i64, usize)>>,
+    cursors: Vec<usize>,
+    heap: BinaryHeap<Reverse<(i64, usize, usize)>>,
+}
+
+impl<'a> ClosestPairs<'a> {
+    const INITIAL_NEIGHBORS: usize = 8;
 
-    pub fn connect_circuits(&mut self, limit: usize) {
-        let edges_to_process: Vec<Edge> = self.edges().iter().take(limit).cloned().collect();
+    fn new(points: &'a [Point]) -> Self {
+        let mut pairs = ClosestPairs {
+            tree: KdTree::new(points),
+            neighbors: vec![Vec::new(); points.len()],
+            cursors: vec![0; points.len()],
+            heap: BinaryHeap::new(),
+        };
 
-        for edge in edges_to_process {
-            self.connect_circuit(&edge);
+        for index in 0..points.len() {
+            pairs.advance(index);
         }
+
+        pairs
     }
 
-    /// Connects two points via a circuit using their indices.
-    /// This method safely modifies the points while maintaining index validity.
-    fn connect_circuit(&mut self, edge: &Edge) {
-        // Check conditions first without borrowing mutably
-        let a_has_circuit = self.points[edge.a_index].circuit.is_some();
-        let b_has_circuit = self.points[edge.b_index].circuit.is_some();
-
-        if !a_has_circuit && !b_has_circuit {
-            let circuit = Rc::new(Circuit {});
-            self.points[edge.a_index].circuit = Some(circuit.clone());
-            self.points[edge.b_index].circuit = Some(circuit);
-        } else if !a_has_circuit || !b_has_circuit {
-            let circuit = self.points[edge.a_index]
-                .circuit
-                .clone()
-                .or_else(|| self.points[edge.b_index].circuit.clone())
-                .unwrap();
-            self.points[edge.a_index].circuit = Some(circuit.clone());
-            self.points[edge.b_index].circuit = Some(circuit);
-        } else {
-            // Both have circuits - merge if they're different
-            let a_circuit = self.points[edge.a_index].circuit.clone();
-            let b_circuit = self.points[edge.b_index].circuit.clone();
-
-            if let (Some(a_circ), Some(b_circ)) = (&a_circuit, &b_circuit)
-                && !Rc::ptr_eq(a_circ, b_circ)
-            {
-                let target_circuit = a_circuit.unwrap();
-                let old_circuit = b_circ.clone();
-
-                // Replace all instances of old_circuit with target_circuit
-                for point in self.points.iter_mut() {
-                    if let Some(ref circuit) = point.circuit
-                        && Rc::ptr_eq(circuit, &old_circuit)
-                    {
-                        point.circuit = Some(target_circuit.clone());
-                    }
+    /// Queue the next pair of `index` with a higher index point, if there is one.
+    fn advance(&mut self, index: usize) {
+        loop {
+            let cursor = self.cursors[index];
+            if cursor == self.neighbors[index].len() {
+                let fetched = self.neighbors[index].len();
+                if fetched == self.tree.points.len() - 1 {
+                    return;
                 }
+
+                let k = (fetched * 2).max(Self::INITIAL_NEIGHBORS);
+                self.neighbors[index] = self.tree.nearest(index, k);
+            }
+
+            let (distance_sq, other) = self.neighbors[index][cursor];
+            self.cursors[index] += 1;
+
+            // The pair is queued from the lower index side only
+            if other > index {
+                self.heap.push(Reverse((distance_sq, index, other)));
+                return;
             }
         }
     }
+}
 
-    fn connect_all(&mut self) -> (Point, Point) {
-        let mut result = None;
-        let mut edges_iter = self.edges().to_vec().into_iter();
-        while !self.all_connected() {
-            let edge = edges_iter.next();
-            match edge {
-                Some(ref e) => {
-                    self.connect_circuit(e);
-                }
-                None => unreachable!("Exhausted all edges!"),
-            }
+impl Iterator for ClosestPairs<'_> {
+    type Item = Pair;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((distance_sq, a, b)) = self.heap.pop()?;
+        self.advance(a);
 
-            result = edge;
+        Some(Pair { a, b, distance_sq })
+    }
+}
+
+/// Disjoint sets of junction boxes joined into circuits.
+struct Circuits {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl Circuits {
+    fn new(len: usize) -> Self {
+        Circuits {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            count: len,
         }
-        match result {
-            Some(ref edge) => (
-                self.points[edge.a_index].clone(),
-                self.points[edge.b_index].clone(),
-            ),
-            None => unreachable!("Could not connect all points!"),
+    }
+
+    fn find(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = index;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
         }
+
+        root
     }
 
-    fn all_connected(&self) -> bool {
-        let circuit = self.points[0].circuit.clone();
+    /// Returns whether the two boxes were in different circuits.
+    fn connect(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
 
-        self.points.iter().all(|point| {
-            if let (Some(lhs), Some(rhs)) = (&circuit, &point.circuit)
-                && Rc::ptr_eq(lhs, rhs)
-            {
-                return true;
-            }
-            false
-        })
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+
+        true
+    }
+
+    /// Returns the sizes of all circuits, sorted largest first
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.parent.len())
+            .filter(|index| self.parent[*index] == *index)
+            .map(|index| self.size[index])
+            .collect();
+        sizes.sort_by(|a, b| b.cmp(a));
+        sizes
     }
 }
 
-fn part1(points: Vec<Point>, limit: usize) -> usize {
-    let mut graph = PointGraph::new(points);
+/// Connect the `limit` closest pairs and return the resulting circuit sizes.
+fn circuit_sizes(points: &[Point], limit: usize) -> Vec<usize> {
+    let mut circuits = Circuits::new(points.len());
+    for pair in ClosestPairs::new(points).take(limit) {
+        circuits.connect(pair.a, pair.b);
+    }
+
+    circuits.sizes()
+}
+
+/// Kruskal's algorithm over the streamed pairs, stopping as soon as every box is in one
+/// circuit, so the last pair is the one that joins everything.
+fn minimum_spanning_tree(points: &[Point]) -> Vec<Pair> {
+    let mut circuits = Circuits::new(points.len());
+    let mut tree = Vec::with_capacity(points.len().saturating_sub(1));
 
-    // Process edges safely - indices are guaranteed to remain valid
-    graph.connect_circuits(limit);
+    for pair in ClosestPairs::new(points) {
+        if circuits.count == 1 {
+            break;
+        }
 
-    let circuit_sizes = graph.circuit_sizes();
-    circuit_sizes.iter().take(3).product()
+        if circuits.connect(pair.a, pair.b) {
+            tree.push(pair);
+        }
+    }
+
+    tree
 }
 
-fn part2(points: Vec<Point>) -> isize {
-    let mut graph = PointGraph::new(points);
+fn part1(points: Vec<Point>, limit: usize) -> usize {
+    circuit_sizes(&points, limit).iter().take(3).product()
+}
 
-    let (a, b) = graph.connect_all();
+fn part2(points: Vec<Point>) -> isize {
+    let tree = minimum_spanning_tree(&points);
+    let last = tree.last().expect("Expected at least two junction boxes");
 
-    a.x * b.x
+    points[last.a].x * points[last.b].x
 }
 
 fn main() {
@@ -220,7 +304,7 @@ fn main() {
     );
 
     println!(
-        "Solution for part 1 is {}",
+        "Solution for part 2 is {}",
         part2(parse_lines(read_lines_from_file("input.txt")))
     );
 }
@@ -231,9 +315,9 @@ fn parse_lines(lines: Vec<String>) -> Vec<Point> {
 
 #[cfg(test)]
 mod test {
-    use super::parse_lines;
-    use super::part1;
-    use super::part2;
+    use itertools::Itertools;
+
+    use super::{ClosestPairs, Pair, parse_lines, part1, part2};
 
     const EXAMPLE: [&str; 20] = [
         "162,817,812",
@@ -273,4 +357,34 @@ mod test {
             25272
         );
     }
+
+    #[test]
+    fn pairs_in_exact_order() {
+        let points = parse_lines(EXAMPLE.map(String::from).to_vec());
+
+        let mut expected: Vec<Pair> = (0..points.len())
+            .tuple_combinations()
+            .map(|(a, b)| Pair {
+                a,
+                b,
+                distance_sq: points[a].distance_sq(&points[b]),
+            })
+            .collect();
+        expected.sort_by_key(|pair| (pair.distance_sq, pair.a, pair.b));
+
+        assert_eq!(ClosestPairs::new(&points).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn pairs_with_equal_distances() {
+        let lines = ["0,0,0", "1,0,0", "2,0,0", "0,1,0", "1,1,0"].map(String::from);
+        let points = parse_lines(lines.to_vec());
+        let distances: Vec<(i64, usize, usize)> = ClosestPairs::new(&points)
+            .map(|pair| (pair.distance_sq, pair.a, pair.b))
+            .collect();
+
+        assert_eq!(distances.len(), 10);
+        assert!(distances.is_sorted());
+        assert_eq!(distances[..2], [(1, 0, 1), (1, 0, 3)]);
+    }
 }
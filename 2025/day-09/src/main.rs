@@ -1,9 +1,12 @@
-use std::fmt::Display;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+};
 
 use itertools::Itertools;
 use rust_aoc_utils::read_lines_from_file;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 struct Tile {
     x: usize,
     y: usize,
@@ -12,7 +15,7 @@ struct Tile {
 impl From<&String> for Tile {
     fn from(s: &String) -> Self {
         let parts = s
-            .split(",")
+            .split(',')
             .map(|part| part.parse::<usize>().unwrap())
             .collect::<Vec<_>>();
 
@@ -33,42 +36,195 @@ fn tile_area(a: &Tile, b: &Tile) -> usize {
     (a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1)
 }
 
-fn part1(lines: Vec<String>) -> usize {
-    let mut result = 0;
-    let tiles = lines.iter().map(Tile::from).collect::<Vec<_>>();
-    let combos = tiles.iter().combinations(2);
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Rectangle {
+    corners: [Tile; 2],
+    area: usize,
+}
+
+impl Rectangle {
+    fn new(a: Tile, b: Tile) -> Self {
+        Rectangle {
+            corners: [a, b],
+            area: tile_area(&a, &b),
+        }
+    }
+}
+
+impl Display for Rectangle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} to {} with area {}",
+            self.corners[0], self.corners[1], self.area
+        )
+    }
+}
+
+/// Every rectangle with red tiles at two opposite corners.
+fn rectangles(tiles: &[Tile]) -> impl Iterator<Item = Rectangle> + '_ {
+    tiles
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| Rectangle::new(*a, *b))
+}
+
+/// Compressed positions along one axis, keeping every red tile coordinate as its own
+/// index and collapsing each run of tiles between two of them into a single index.
+///
+/// Index 0 and the last index are padding outside of every tile.
+#[derive(Debug)]
+struct Axis {
+    index: HashMap<usize, usize>,
+    len: usize,
+}
 
-    for combo in combos {
-        let a = combo[0];
-        let b = combo[1];
+impl Axis {
+    fn new(mut coords: Vec<usize>) -> Self {
+        coords.sort();
+        coords.dedup();
+
+        let mut index = HashMap::new();
+        let mut next = 1;
+        for (pos, coord) in coords.iter().enumerate() {
+            if pos > 0 && coord - coords[pos - 1] > 1 {
+                next += 1;
+            }
+            index.insert(*coord, next);
+            next += 1;
+        }
 
-        let area = tile_area(a, b);
-        println!("[{:?}, {:?}]: {:?}", a, b, area);
-        if area > result {
-            result = area;
+        Axis {
+            index,
+            len: next + 1,
         }
     }
 
-    result
+    fn span(&self, a: usize, b: usize) -> std::ops::RangeInclusive<usize> {
+        let (a, b) = (self.index[&a], self.index[&b]);
+        a.min(b)..=a.max(b)
+    }
+}
+
+/// The floor inside the loop of red tiles, on a compressed grid with a prefix sum of
+/// cells outside the loop so any rectangle can be checked in constant time.
+#[derive(Debug)]
+struct Floor {
+    xs: Axis,
+    ys: Axis,
+    outside_before: Vec<Vec<usize>>,
 }
 
-fn part2(lines: Vec<String>) -> usize {
-    let mut result = 0;
+impl Floor {
+    /// Consecutive red tiles, wrapping around, must share a row or column.
+    fn new(tiles: &[Tile]) -> Self {
+        let xs = Axis::new(tiles.iter().map(|tile| tile.x).collect());
+        let ys = Axis::new(tiles.iter().map(|tile| tile.y).collect());
+
+        let mut boundary = vec![vec![false; xs.len]; ys.len];
+        for (a, b) in tiles.iter().circular_tuple_windows() {
+            assert!(
+                a.x == b.x || a.y == b.y,
+                "Red tiles {a} and {b} do not share a row or column"
+            );
+
+            for y in ys.span(a.y, b.y) {
+                for x in xs.span(a.x, b.x) {
+                    boundary[y][x] = true;
+                }
+            }
+        }
+
+        // Flood fill from the padding, which is always outside
+        let mut outside = vec![vec![false; xs.len]; ys.len];
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::from([(0, 0)]);
+        outside[0][0] = true;
+        while let Some((y, x)) = queue.pop_front() {
+            let neighbors = [
+                (y.wrapping_sub(1), x),
+                (y + 1, x),
+                (y, x.wrapping_sub(1)),
+                (y, x + 1),
+            ];
+            for (ny, nx) in neighbors {
+                if ny < ys.len && nx < xs.len && !boundary[ny][nx] && !outside[ny][nx] {
+                    outside[ny][nx] = true;
+                    queue.push_back((ny, nx));
+                }
+            }
+        }
+
+        let mut outside_before = vec![vec![0; xs.len + 1]; ys.len + 1];
+        for y in 0..ys.len {
+            for x in 0..xs.len {
+                outside_before[y + 1][x + 1] = outside_before[y][x + 1] + outside_before[y + 1][x]
+                    - outside_before[y][x]
+                    + outside[y][x] as usize;
+            }
+        }
 
-    result
+        Floor {
+            xs,
+            ys,
+            outside_before,
+        }
+    }
+
+    fn contains(&self, rectangle: &Rectangle) -> bool {
+        let [a, b] = rectangle.corners;
+        let (xs, ys) = (self.xs.span(a.x, b.x), self.ys.span(a.y, b.y));
+        let (x0, x1, y0, y1) = (*xs.start(), *xs.end() + 1, *ys.start(), *ys.end() + 1);
+
+        let outside = self.outside_before[y1][x1] + self.outside_before[y0][x0]
+            - self.outside_before[y0][x1]
+            - self.outside_before[y1][x0];
+
+        outside == 0
+    }
+}
+
+/// Largest rectangle with red tiles at two opposite corners that lies entirely within
+/// the loop of red tiles.
+fn largest_inside(tiles: &[Tile]) -> Option<Rectangle> {
+    let floor = Floor::new(tiles);
+
+    rectangles(tiles)
+        .filter(|rectangle| floor.contains(rectangle))
+        .max_by_key(|rectangle| rectangle.area)
+}
+
+fn part1(lines: Vec<String>) -> usize {
+    let tiles = lines.iter().map(Tile::from).collect::<Vec<_>>();
+
+    rectangles(&tiles)
+        .map(|rectangle| rectangle.area)
+        .max()
+        .unwrap_or(0)
+}
+
+fn part2(lines: Vec<String>) -> Option<Rectangle> {
+    let tiles = lines.iter().map(Tile::from).collect::<Vec<_>>();
+
+    largest_inside(&tiles)
 }
 
 fn main() {
     println!("Part 1: {}", part1(read_lines_from_file("input.txt")));
-    println!("Part 2: {}", part2(read_lines_from_file("input.txt")));
+
+    match part2(read_lines_from_file("input.txt")) {
+        Some(rectangle) => {
+            println!("Part 2: {}", rectangle.area);
+            println!("Largest rectangle inside is {rectangle}");
+        }
+        None => println!("Part 2: no rectangle fits inside"),
+    }
 }
 
 // Utilities
 
 #[cfg(test)]
 mod test {
-    use super::part1;
-    use super::part2;
+    use super::{Rectangle, Tile, largest_inside, part1, part2};
 
     const EXAMPLE: [&str; 8] = ["7,1", "11,1", "11,7", "9,7", "9,5", "2,5", "2,3", "7,3"];
 
@@ -78,7 +234,29 @@ mod test {
     }
 
     #[test]
-    fn _solve_example_part2() {
-        assert_eq!(part2(EXAMPLE.map(String::from).to_vec()), 14);
+    fn solve_example_part2() {
+        let rectangle = part2(EXAMPLE.map(String::from).to_vec());
+        assert_eq!(rectangle.map(|rectangle| rectangle.area), Some(24));
+    }
+
+    #[test]
+    fn largest_inside_corners() {
+        let tiles: Vec<Tile> = EXAMPLE.iter().map(|s| Tile::from(&s.to_string())).collect();
+
+        assert_eq!(
+            largest_inside(&tiles),
+            Some(Rectangle::new(Tile { x: 9, y: 5 }, Tile { x: 2, y: 3 }))
+        );
+    }
+
+    #[test]
+    fn adjacent_edges_without_gap() {
+        // A notch one tile wide leaves no outside tiles between its edges
+        let tiles: Vec<Tile> = ["0,0", "4,0", "4,4", "3,4", "3,1", "2,1", "2,4", "0,4"]
+            .iter()
+            .map(|s| Tile::from(&s.to_string()))
+            .collect();
+
+        assert_eq!(largest_inside(&tiles).map(|r| r.area), Some(25));
     }
 }
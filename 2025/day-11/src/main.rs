@@ -1,60 +1,206 @@
 use rust_aoc_utils::read_lines_from_file;
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
-#[derive(Debug, Hash, Eq, PartialEq)]
-struct PathKey(String, bool, bool);
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathError {
+    UnknownDevice(String),
+    /// Device names around a cycle, starting and ending at the same device
+    Cycle(Vec<String>),
+    TooManyWaypoints(usize),
+}
 
-fn part1(lines: Vec<String>) -> usize {
-    let graph = parse(lines);
-    let mut cache: HashMap<PathKey, usize> = HashMap::new();
-    find_path("you", true, true, &graph, &mut cache)
+impl Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathError::UnknownDevice(name) => write!(f, "unknown device '{name}'"),
+            PathError::Cycle(names) => write!(f, "devices form a cycle: {}", names.join(" -> ")),
+            PathError::TooManyWaypoints(count) => {
+                write!(
+                    f,
+                    "{count} waypoints requested, at most {} are supported",
+                    Graph::MAX_WAYPOINTS
+                )
+            }
+        }
+    }
 }
 
-fn part2(lines: Vec<String>) -> usize {
-    let graph = parse(lines);
-    let mut cache: HashMap<PathKey, usize> = HashMap::new();
-    find_path("svr", false, false, &graph, &mut cache)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PathCount {
+    count: usize,
+    /// One of the counted paths, from source to sink, if there are any
+    example: Option<Vec<String>>,
+}
+
+/// Devices and their outputs, with names interned to ids in order of first appearance.
+#[derive(Debug, Default)]
+struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    outputs: Vec<Vec<usize>>,
 }
 
-fn find_path(
-    current: &str,
-    dac: bool,
-    fft: bool,
-    graph: &HashMap<String, Vec<String>>,
-    cache: &mut HashMap<PathKey, usize>,
-) -> usize {
-    if current == "out" {
-        let result = match dac && fft {
-            true => 1,
-            false => 0,
-        };
-
-        return result;
+impl Graph {
+    const MAX_WAYPOINTS: usize = 16;
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.outputs.push(Vec::new());
+        id
     }
 
-    match graph.get(current) {
-        Some(nexts) => nexts
-            .iter()
-            .map(|next| {
-                let visited_dac = dac || current == "dac";
-                let visited_fft = fft || current == "fft";
-                let key = PathKey(next.to_string(), visited_dac, visited_fft);
-                match cache.get(&key) {
-                    Some(value) => *value,
-                    None => {
-                        let value = find_path(next, visited_dac, visited_fft, graph, cache);
-                        cache.insert(key, value);
-                        value
+    fn id(&self, name: &str) -> Result<usize, PathError> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or_else(|| PathError::UnknownDevice(name.to_string()))
+    }
+
+    /// Devices reachable from `source`, each after all of its outputs.
+    fn post_order(&self, source: usize) -> Result<Vec<usize>, PathError> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            Unvisited,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::Unvisited; self.names.len()];
+        let mut order = Vec::new();
+        // Devices on the current path, with the next output of each to visit
+        let mut path: Vec<(usize, usize)> = vec![(source, 0)];
+        state[source] = State::OnPath;
+
+        while let Some((id, next)) = path.last_mut() {
+            let id = *id;
+            match self.outputs[id].get(*next) {
+                Some(&output) => {
+                    *next += 1;
+                    match state[output] {
+                        State::Unvisited => {
+                            state[output] = State::OnPath;
+                            path.push((output, 0));
+                        }
+                        State::OnPath => {
+                            let start = path.iter().position(|(id, _)| *id == output).unwrap();
+                            let mut names: Vec<String> = path[start..]
+                                .iter()
+                                .map(|(id, _)| self.names[*id].clone())
+                                .collect();
+                            names.push(self.names[output].clone());
+                            return Err(PathError::Cycle(names));
+                        }
+                        State::Done => {}
                     }
                 }
-            })
-            .sum::<usize>(),
-        None => {
-            unreachable!("Reached end at {}", current)
+                None => {
+                    state[id] = State::Done;
+                    order.push(id);
+                    path.pop();
+                }
+            }
         }
+
+        Ok(order)
+    }
+
+    /// Count the paths from `source` to `sink` that pass through every one of `waypoints`.
+    ///
+    /// Each device tracks a count for every subset of waypoints still to be visited, so the
+    /// work grows with `2^waypoints` rather than with the number of paths.
+    fn count_paths(
+        &self,
+        source: &str,
+        sink: &str,
+        waypoints: &[&str],
+    ) -> Result<PathCount, PathError> {
+        if waypoints.len() > Self::MAX_WAYPOINTS {
+            return Err(PathError::TooManyWaypoints(waypoints.len()));
+        }
+
+        let (source, sink) = (self.id(source)?, self.id(sink)?);
+        let mut waypoint_bits = vec![0_usize; self.names.len()];
+        for (bit, waypoint) in waypoints.iter().enumerate() {
+            waypoint_bits[self.id(waypoint)?] |= 1 << bit;
+        }
+
+        let all = (1 << waypoints.len()) - 1;
+        // counts[id][remaining] is the number of paths from `id` to the sink that visit
+        // every waypoint in `remaining`, including `id` itself
+        let mut counts: Vec<Vec<usize>> = vec![Vec::new(); self.names.len()];
+        for id in self.post_order(source)? {
+            counts[id] = (0..=all)
+                .map(|remaining: usize| {
+                    let remaining = remaining & !waypoint_bits[id];
+                    match id == sink {
+                        true => (remaining == 0) as usize,
+                        false => self.outputs[id]
+                            .iter()
+                            .map(|output| counts[*output][remaining])
+                            .sum(),
+                    }
+                })
+                .collect();
+        }
+
+        let count = counts[source][all];
+        let example = (count > 0).then(|| {
+            let mut path = vec![self.names[source].clone()];
+            let (mut id, mut remaining) = (source, all);
+            while id != sink {
+                remaining &= !waypoint_bits[id];
+                id = *self.outputs[id]
+                    .iter()
+                    .find(|output| counts[**output][remaining] > 0)
+                    .unwrap();
+                path.push(self.names[id].clone());
+            }
+            path
+        });
+
+        Ok(PathCount { count, example })
     }
 }
 
+impl From<Vec<String>> for Graph {
+    fn from(lines: Vec<String>) -> Self {
+        let mut graph = Graph::default();
+        for line in lines {
+            let (device, outputs) = line
+                .split_once(": ")
+                .unwrap_or_else(|| panic!("Expected '<device>: <outputs>', found '{line}'"));
+
+            let id = graph.intern(device);
+            for output in outputs.split_ascii_whitespace() {
+                let output = graph.intern(output);
+                graph.outputs[id].push(output);
+            }
+        }
+        graph
+    }
+}
+
+fn count_or_panic(graph: &Graph, source: &str, sink: &str, waypoints: &[&str]) -> usize {
+    match graph.count_paths(source, sink, waypoints) {
+        Ok(paths) => paths.count,
+        Err(err) => panic!("Cannot count paths from {source} to {sink}: {err}"),
+    }
+}
+
+fn part1(lines: Vec<String>) -> usize {
+    count_or_panic(&Graph::from(lines), "you", "out", &[])
+}
+
+fn part2(lines: Vec<String>) -> usize {
+    count_or_panic(&Graph::from(lines), "svr", "out", &["dac", "fft"])
+}
+
 fn main() {
     println!(
         "Solution for part 1 is {}",
@@ -66,23 +212,9 @@ fn main() {
     );
 }
 
-// Utilities
-
-fn parse(lines: Vec<String>) -> HashMap<String, Vec<String>> {
-    let mut map = HashMap::new();
-    for line in lines {
-        let parts: Vec<&str> = line.split(": ").collect();
-        let key = parts[0].to_string();
-        let values = parts[1].split(" ").map(String::from).collect();
-        map.insert(key, values);
-    }
-    map
-}
-
 #[cfg(test)]
 mod test {
-    use super::part1;
-    use super::part2;
+    use super::{Graph, PathError, part1, part2};
 
     const EXAMPLE_1: [&str; 10] = [
         "aaa: you hhh",
@@ -122,4 +254,46 @@ mod test {
     fn solve_example_part2() {
         assert_eq!(part2(EXAMPLE_2.map(String::from).to_vec()), 2);
     }
+
+    #[test]
+    fn example_path_through_waypoints() {
+        let graph = Graph::from(EXAMPLE_2.map(String::from).to_vec());
+        let paths = graph.count_paths("svr", "out", &["fft", "dac"]).unwrap();
+
+        assert_eq!(paths.count, 2);
+        assert_eq!(
+            paths.example.unwrap(),
+            vec![
+                "svr", "aaa", "fft", "ccc", "eee", "dac", "fff", "ggg", "out"
+            ]
+        );
+        assert_eq!(graph.count_paths("svr", "out", &[]).unwrap().count, 8);
+        assert_eq!(
+            graph
+                .count_paths("svr", "out", &["hub", "dac"])
+                .unwrap()
+                .count,
+            0
+        );
+    }
+
+    #[test]
+    fn count_path_errors() {
+        let lines = ["aaa: bbb", "bbb: ccc out", "ccc: aaa"].map(String::from);
+        let graph = Graph::from(lines.to_vec());
+
+        assert_eq!(
+            graph.count_paths("aaa", "out", &[]),
+            Err(PathError::Cycle(vec![
+                "aaa".to_string(),
+                "bbb".to_string(),
+                "ccc".to_string(),
+                "aaa".to_string()
+            ]))
+        );
+        assert_eq!(
+            graph.count_paths("aaa", "zzz", &[]),
+            Err(PathError::UnknownDevice("zzz".to_string()))
+        );
+    }
 }
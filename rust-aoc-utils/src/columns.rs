@@ -0,0 +1,117 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnError {
+    /// A line had a different number of whitespace-separated values than expected
+    WrongColumnCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A value could not be parsed into the column type
+    InvalidValue {
+        line: usize,
+        column: usize,
+        value: String,
+    },
+}
+
+impl Display for ColumnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnError::WrongColumnCount {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line}: expected {expected} columns, found {found}"),
+            ColumnError::InvalidValue {
+                line,
+                column,
+                value,
+            } => write!(f, "line {line}, column {column}: invalid value '{value}'"),
+        }
+    }
+}
+
+impl std::error::Error for ColumnError {}
+
+/// Parse lines of `num_columns` whitespace-separated values into one vector per column.
+pub fn parse_columns<T: FromStr>(
+    lines: &[String],
+    num_columns: usize,
+) -> Result<Vec<Vec<T>>, ColumnError> {
+    let mut columns: Vec<Vec<T>> = (0..num_columns)
+        .map(|_| Vec::with_capacity(lines.len()))
+        .collect();
+
+    for (line_num, line) in lines.iter().enumerate() {
+        let values: Vec<&str> = line.split_ascii_whitespace().collect();
+        if values.len() != num_columns {
+            return Err(ColumnError::WrongColumnCount {
+                line: line_num,
+                expected: num_columns,
+                found: values.len(),
+            });
+        }
+
+        for (column, value) in values.into_iter().enumerate() {
+            let parsed = value.parse::<T>().map_err(|_| ColumnError::InvalidValue {
+                line: line_num,
+                column,
+                value: value.to_string(),
+            })?;
+            columns[column].push(parsed);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Like `parse_columns`, with the number of columns fixed so they can be destructured.
+pub fn parse_n_columns<T: FromStr, const N: usize>(
+    lines: &[String],
+) -> Result<[Vec<T>; N], ColumnError> {
+    let columns = parse_columns(lines, N)?;
+
+    // The column count was validated while parsing
+    Ok(columns
+        .try_into()
+        .unwrap_or_else(|_| unreachable!("Expected {N} columns")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_columns() {
+        let lines = vec!["3   4".to_string(), "4   3".to_string()];
+        let [first, second] = parse_n_columns::<i64, 2>(&lines).unwrap();
+
+        assert_eq!(first, vec![3, 4]);
+        assert_eq!(second, vec![4, 3]);
+    }
+
+    #[test]
+    fn test_parse_columns_errors() {
+        let lines = vec!["1 2 3".to_string(), "4 5".to_string()];
+        assert_eq!(
+            parse_columns::<u32>(&lines, 3),
+            Err(ColumnError::WrongColumnCount {
+                line: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+
+        let lines = vec!["1 x".to_string()];
+        assert_eq!(
+            parse_columns::<u32>(&lines, 2),
+            Err(ColumnError::InvalidValue {
+                line: 0,
+                column: 1,
+                value: "x".to_string()
+            })
+        );
+    }
+}
@@ -3,11 +3,13 @@
 //! This crate provides common data structures and algorithms that are frequently
 //! needed when solving Advent of Code puzzles.
 
+pub mod columns;
 pub mod file;
 pub mod grid;
 pub mod ranges;
 
 // Re-export commonly used items for convenience
+pub use columns::{parse_columns, parse_n_columns};
 pub use file::read_lines_from_file;
 pub use grid::Coordinate;
 pub use ranges::ranges_overlap;